@@ -1,91 +1,80 @@
-extern crate aocf;
+use std::{env, fs::File, io::BufReader, process, time::Instant};
 
-use std::{collections::HashMap, iter::zip};
-
-use aocf::Aoc;
+use aoc_2024::{
+    input, report,
+    years::y2024::day01::{distance2, merged_distance2, read_lists, top_contributors, Day01},
+};
 
 fn main() {
-    let mut aoc = Aoc::new()
-        .year(Some(2024))
-        .day(Some(1))
-        .cookie_file("./examples/cookie")
-        .init()
-        .unwrap();
-
-    let input = if let Ok(i) = aoc.get_input(false) {
-        i
-    } else {
-        "you probably need to add a valid cookie".to_string()
+    let args = env::args().collect::<Vec<_>>();
+    let file = args.iter().skip_while(|a| *a != "--file").nth(1);
+
+    // huge inputs are streamed straight from the file
+    let (result, input) = match file {
+        Some(path) => (
+            File::open(path).and_then(|f| read_lists(BufReader::new(f))),
+            None,
+        ),
+        None => {
+            let (config, input) = match input::load_day(2024, 1) {
+                Ok(loaded) => loaded,
+                Err(e) => {
+                    eprintln!("{}", e);
+                    return;
+                }
+            };
+            (read_lists(input.as_bytes()), Some((config, input)))
+        }
     };
 
-    let (list1, list2) = parse_data(&input).unwrap();
-    let sum1 = distance1(&list1, &list2);
-    let sum2 = distance2(&list1, &list2);
-
-    println!("day 1 - part 1: {}", sum1);
-    println!("day 1 - part 2: {}", sum2);
-}
-
-fn parse_data(input: &str) -> Result<(Vec<u32>, Vec<u32>), Box<dyn std::error::Error>> {
-    let data: (Vec<_>, Vec<_>) = input
-        .lines()
-        .map(|l| {
-            let v = l.split_whitespace().collect::<Vec<&str>>();
-            return (v[0].parse::<u32>().unwrap(), v[1].parse::<u32>().unwrap());
-        })
-        .unzip();
-
-    Ok((data.0, data.1))
-}
-
-pub fn distance1(list1: &[u32], list2: &[u32]) -> u32 {
-    let mut list1 = list1.to_vec();
-    let mut list2 = list2.to_vec();
-    list1.sort();
-    list2.sort();
-
-    let sum: u32 = zip(list1, list2)
-        .into_iter()
-        .map(|(a, b)| a.abs_diff(b))
-        .sum();
-
-    sum
-}
+    let (lists, issues) = match result {
+        Ok(read) => read,
+        Err(e) => {
+            eprintln!("day 1 - {}: {}", file.map_or("input", |f| f.as_str()), e);
+            process::exit(1);
+        }
+    };
+    for issue in issues.iter() {
+        eprintln!("day 1 - {}", issue);
+    }
+    if args.iter().any(|a| a == "--strict") && issues.iter().any(|i| i.is_error()) {
+        eprintln!("day 1 - input is invalid, refusing to compute answers in strict mode");
+        process::exit(1);
+    }
 
-pub fn distance2(list1: &[u32], list2: &[u32]) -> u32 {
-    let mut occurences: HashMap<u32, u32> = HashMap::new();
-    list2.iter().for_each(|v| {
-        occurences
-            .entry(*v)
-            .and_modify(|amount| *amount += 1)
-            .or_insert(1);
-    });
+    match input {
+        Some((config, input)) => report::print_day(&config, &Day01, input),
+        None => {
+            println!("day 1 - part 1: {}", lists.distance1());
+            println!("day 1 - part 2: {}", lists.distance2());
+        }
+    }
 
-    let sum: u32 = list1
+    if let Some(top) = args
         .iter()
-        .map(|v| v * occurences.get(&v).or(Some(&0)).unwrap())
-        .sum();
-
-    sum
-}
-
-#[cfg(test)]
-mod tests {
-    use super::{distance1, distance2};
-
-    #[test]
-    fn test_day1_part1() {
-        let list1 = vec![3, 4, 2, 1, 3, 3];
-        let list2 = vec![4, 3, 5, 3, 9, 3];
-
-        assert_eq!(distance1(&list1, &list2), 11);
+        .skip_while(|a| *a != "--top")
+        .nth(1)
+        .and_then(|n| n.parse::<usize>().ok())
+    {
+        println!(
+            "{:>10} {:>8} {:>8} {:>14}",
+            "id", "left", "right", "contribution"
+        );
+        for s in top_contributors(&lists.breakdown(), top) {
+            println!(
+                "{:>10} {:>8} {:>8} {:>14}",
+                s.id, s.left, s.right, s.contribution
+            );
+        }
     }
 
-    #[test]
-    fn test_day1_part2() {
-        let list1 = vec![3, 4, 2, 1, 3, 3];
-        let list2 = vec![4, 3, 5, 3, 9, 3];
-
-        assert_eq!(distance2(&list1, &list2), 31);
+    if args.iter().any(|a| a == "--compare") {
+        let (left, right) = lists.to_vecs();
+        let start = Instant::now();
+        let hashed = distance2(&left, &right);
+        println!("frequency maps: {} in {:.2?}", hashed, start.elapsed());
+        let start = Instant::now();
+        let merged = merged_distance2(&left, &right);
+        println!("merged lists:   {} in {:.2?}", merged, start.elapsed());
     }
 }
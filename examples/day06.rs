@@ -1,171 +1,65 @@
-extern crate aocf;
+use std::{env, time::Duration};
 
-use std::collections::HashSet;
-
-use aocf::Aoc;
+use aoc_2024::{
+    input, report,
+    years::y2024::day06::{animate, print_every, Day06, Game},
+};
 
 fn main() {
-    let mut aoc = Aoc::new()
-        .year(Some(2024))
-        .day(Some(6))
-        .cookie_file("./examples/cookie")
-        .init()
-        .unwrap();
-
-    let input = if let Ok(i) = aoc.get_input(false) {
-        i
-    } else {
-        "you probably need to add a valid cookie".to_string()
+    let (config, input) = match input::load_day(2024, 6) {
+        Ok(loaded) => loaded,
+        Err(e) => {
+            eprintln!("{}", e);
+            return;
+        }
     };
 
-    let mut game = Game::from(&input);
-    while game.guard.is_some() {
-        game.step();
-    }
-    println!("day 6 - part 1: {}", game.visited.len());
-    // println!("day 4 - part 2: {}", match_count2);
-}
-
-type Position = (usize, usize);
-
-#[derive(Debug, PartialEq, Clone, Copy)]
-enum Direction {
-    North,
-    East,
-    West,
-    South,
-}
-
-static DIRECTIONS: [Direction; 4] = [
-    Direction::North,
-    Direction::East,
-    Direction::South,
-    Direction::West,
-];
+    let args = env::args().collect::<Vec<_>>();
+    let show_path = args.iter().any(|a| a == "--path");
+    let export_csv = args.iter().any(|a| a == "--csv");
 
-#[derive(Debug)]
-struct Game {
-    collides: u32,
-    board: (usize, usize),
-    guard: Option<Position>,
-    crates: HashSet<(usize, usize)>,
-    visited: HashSet<(usize, usize)>,
-}
-
-impl Game {
-    fn from(input: &str) -> Self {
-        let height = input.lines().collect::<Vec<_>>().len();
-        let width = input.lines().nth(1).unwrap().trim().len();
-
-        let mut crates = HashSet::new();
-        let mut visited = HashSet::new();
-        let mut guard = Some((0, 0));
-
-        for (j, row) in input.lines().enumerate() {
-            for (i, ch) in row.chars().enumerate() {
-                if ch == '#' {
-                    crates.insert((i, j));
-                }
-                if ch == '^' {
-                    guard = Some((i, j));
-                    visited.insert((i, j));
-                }
+    let option = |name: &str| {
+        args.iter()
+            .skip_while(|a| *a != name)
+            .nth(1)
+            .and_then(|v| v.parse::<u64>().ok())
+    };
+    let every = option("--every");
+    let animated = args.iter().any(|a| a == "--animate");
+
+    if show_path || export_csv || every.is_some() || animated {
+        let mut game = match Game::from(&input) {
+            Ok(game) => game,
+            Err(e) => {
+                eprintln!("day 6 - invalid map: {}", e);
+                return;
             }
+        };
+        if show_path || export_csv {
+            game.record_path();
         }
 
-        Self {
-            collides: 0,
-            board: (width, height),
-            guard,
-            crates,
-            visited,
+        if let Some(every) = every {
+            print_every(&mut game, every.max(1) as usize);
+        } else if animated {
+            let delay = Duration::from_millis(option("--delay").unwrap_or(100));
+            animate(&mut game, delay);
         }
-    }
-    fn step(&mut self) {
-        let next_position = self.get_next_position();
 
-        if let Some(position) = next_position {
-            if self.crates.contains(&position) {
-                self.collides += 1;
-            } else {
-                self.guard = next_position;
-                self.visited.insert(next_position.unwrap());
-            }
-        } else {
-            self.guard = None;
+        while game.is_patrolling() {
+            game.step();
         }
-    }
-
-    fn get_current_direction(&self) -> Direction {
-        DIRECTIONS[self.collides as usize % DIRECTIONS.len()]
-    }
-
-    fn get_next_position(&self) -> Option<Position> {
-        let current_direction = self.get_current_direction();
-        let (x, y) = self.guard.unwrap();
-        let (width, height) = self.board;
-        match current_direction {
-            Direction::North => {
-                if y == 0 {
-                    return None;
-                } else {
-                    return Some((x, y - 1));
-                }
-            }
-            Direction::South => {
-                if y == height - 1 {
-                    return None;
-                } else {
-                    return Some((x, y + 1));
-                }
-            }
-            Direction::East => {
-                if x == width - 1 {
-                    return None;
-                } else {
-                    return Some((x + 1, y));
-                }
-            }
-            Direction::West => {
-                if x == 0 {
-                    return None;
-                } else {
-                    return Some((x - 1, y));
-                }
-            }
+        if game.is_looping() {
+            eprintln!("day 6 - the guard patrols in a loop and never leaves");
         }
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use crate::{Direction, Game};
-
-    #[test]
-    fn test_parse_data() {
-        let input = r"....#.....
-.........#
-..........
-..#.......
-.......#..
-..........
-.#..^.....
-........#.
-#.........
-......#...";
-
-        let mut game = Game::from(&input);
-        assert_eq!(game.guard, Some((4, 6)));
-        assert_eq!(game.get_current_direction(), Direction::North);
-        assert_eq!(game.crates.len(), 8);
-        assert!(game.crates.contains(&(4, 0)));
-        assert!(game.crates.contains(&(1, 6)));
-        assert!(game.crates.contains(&(6, 9)));
-
-        while game.guard.is_some() {
-            game.step();
+        if show_path {
+            println!("{}", game.render_path());
+            println!("turns: {}", game.turns());
+        }
+        if export_csv {
+            print!("{}", game.path_csv());
         }
-
-        assert_eq!(game.visited.len(), 41);
     }
+
+    report::print_day(&config, &Day06, input);
 }
@@ -1,211 +1,73 @@
-extern crate aocf;
+use std::{env, fs};
 
-use std::{
-    collections::{HashMap, HashSet},
-    vec,
+use aoc_2024::{
+    input, report,
+    years::y2024::day08::{diff_maps, render, solve_filtered, solve_with, AntinodeRule, Day08},
 };
 
-use aocf::Aoc;
-
 fn main() {
-    let mut aoc = Aoc::new()
-        .year(Some(2024))
-        .day(Some(8))
-        .cookie_file("./examples/cookie")
-        .init()
-        .unwrap();
-
-    let input = if let Ok(i) = aoc.get_input(false) {
-        i
-    } else {
-        "you probably need to add a valid cookie".to_string()
+    let (config, input) = match input::load_day(2024, 8) {
+        Ok(loaded) => loaded,
+        Err(e) => {
+            eprintln!("{}", e);
+            return;
+        }
     };
 
-    let solution = solve(&input);
-    println!("day 8 - part 1: {}", solution.len());
+    report::print_day(&config, &Day08, input.clone());
 
-    let solution2 = solve2(&input);
-    println!("day 8 - part 2: {}", solution2.len());
-}
-
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-struct Point {
-    x: i32,
-    y: i32,
-}
+    let args = env::args().collect::<Vec<_>>();
+    let option = |name: &str| args.iter().skip_while(|a| *a != name).nth(1);
 
-impl Point {
-    fn new(x: i32, y: i32) -> Self {
-        Point { x, y }
-    }
-
-    fn is_on_grid(&self, board: (u32, u32)) -> bool {
-        let (width, height) = board;
-        self.x >= 0 && self.y >= 0 && self.x < width as i32 && self.y < height as i32
-    }
-
-    fn minus(&self, other: &Point) -> Point {
-        let x_diff = self.x - other.x;
-        let y_diff = self.y - other.y;
-        Point {
-            x: self.x + x_diff,
-            y: self.y + y_diff,
+    if args.iter().any(|a| a == "--resonant") {
+        match solve_with(&input, &AntinodeRule::resonant()) {
+            Ok(resonant) => println!("day 8 - part 2 (gcd reduced): {}", resonant.len()),
+            Err(e) => eprintln!("day 8 - {}", e),
         }
     }
 
-    fn minus2(&self, other: &Point) -> Point {
-        let x_diff = self.x - other.x;
-        let y_diff = self.y - other.y;
-        Point {
-            x: x_diff,
-            y: y_diff,
+    if args.iter().any(|a| a == "--render") {
+        let mut rule = match option("--part").map(|p| p.as_str()) {
+            Some("2") => AntinodeRule::part2(),
+            Some("resonant") => AntinodeRule::resonant(),
+            _ => AntinodeRule::part1(),
+        };
+        let number = |name: &str| option(name).and_then(|v| v.parse::<i32>().ok());
+        if let Some(ratio) = number("--ratio") {
+            rule.ratio = ratio;
+        }
+        if let Some(min) = number("--min-harmonic") {
+            rule.min_harmonic = min;
+        }
+        match option("--max-harmonic").map(|m| m.as_str()) {
+            Some("none") => rule.max_harmonic = None,
+            Some(_) => rule.max_harmonic = number("--max-harmonic").or(rule.max_harmonic),
+            None => {}
+        }
+        let flag = |name: &str| args.iter().any(|a| a == name);
+        rule.antennas |= flag("--antennas");
+        rule.reduce |= flag("--reduce");
+        rule.line |= flag("--line");
+        if let Err(e) = rule.check() {
+            eprintln!("day 8 - {}", e);
+            return;
+        }
+        let frequency = option("--freq").and_then(|f| f.chars().next());
+        let antinodes = match solve_filtered(&input, &rule, frequency) {
+            Ok(antinodes) => antinodes,
+            Err(e) => {
+                eprintln!("day 8 - {}", e);
+                return;
+            }
+        };
+        let map = render(&input, &antinodes, frequency);
+
+        match option("--expected") {
+            Some(path) => match fs::read_to_string(path) {
+                Ok(expected) => println!("{}", diff_maps(&map, &expected)),
+                Err(e) => eprintln!("day 8 - {}: {}", path, e),
+            },
+            None => println!("{}\nantinodes: {}", map, antinodes.len()),
         }
-    }
-}
-
-fn solve(input: &str) -> HashSet<Point> {
-    let mut antinodes = HashSet::new();
-    let data = parse_data(&input);
-    let board = get_board(&input);
-
-    data.iter()
-        .flat_map(|(_, nodes)| {
-            nodes.iter().enumerate().flat_map(|(i, n)| {
-                let mut others = nodes.to_owned();
-                others.remove(i);
-                let diffs = others
-                    .iter()
-                    .map(|other| n.minus(other))
-                    .collect::<Vec<_>>();
-                diffs
-            })
-        })
-        .filter(|p| p.is_on_grid(board))
-        .for_each(|p| {
-            antinodes.insert(p);
-        });
-
-    antinodes
-}
-
-fn solve2(input: &str) -> HashSet<Point> {
-    let mut antinodes = HashSet::new();
-    let data = parse_data(&input);
-    let board = get_board(&input);
-
-    data.iter()
-        .flat_map(|(_, nodes)| {
-            nodes.iter().enumerate().flat_map(|(i, n)| {
-                let mut others = nodes.to_owned();
-                others.remove(i);
-                let diffs = others
-                    .iter()
-                    .flat_map(|other| {
-                        // trackng all nodes in vec
-                        let mut a = vec![];
-                        // count diff
-                        let diff = n.minus2(other);
-
-                        let mut last = n.to_owned();
-                        loop {
-                            if !last.is_on_grid(board) {
-                                break;
-                            }
-                            a.push(last);
-                            last = last.minus2(&diff);
-                        }
-                        a
-                    })
-                    .collect::<Vec<_>>();
-                diffs
-            })
-        })
-        .filter(|p| p.is_on_grid(board))
-        .for_each(|p| {
-            antinodes.insert(p);
-        });
-
-    antinodes
-}
-
-fn parse_data(input: &str) -> HashMap<char, Vec<Point>> {
-    let mut positions: HashMap<char, Vec<Point>> = HashMap::new();
-
-    input.lines().enumerate().for_each(|(j, line)| {
-        line.char_indices()
-            .filter(|(_, ch)| ch.is_alphanumeric())
-            .for_each(|(i, ch)| {
-                let point = Point::new(i as i32, j as i32);
-
-                positions
-                    .entry(ch)
-                    .and_modify(|ch| ch.push(point))
-                    .or_insert(vec![point]);
-            });
-    });
-
-    positions
-}
-
-fn get_board(input: &str) -> (u32, u32) {
-    let width = input
-        .lines()
-        .collect::<Vec<&str>>()
-        .first()
-        .unwrap()
-        .chars()
-        .count();
-    let height = input.lines().count();
-    (width as u32, height as u32)
-}
-
-#[cfg(test)]
-mod tests {
-
-    use crate::{parse_data, solve, solve2, Point};
-
-    #[test]
-    fn test_solution() {
-        let input = r"............
-........0...
-.....0......
-.......0....
-....0.......
-......A.....
-............
-............
-........A...
-.........A..
-............
-............";
-
-        let data = parse_data(&input);
-        assert_eq!(data.len(), 2);
-        assert_eq!(data.get(&'A').is_some_and(|d| { d.len() == 3 }), true);
-
-        let solution = solve(&input);
-        assert_eq!(solution.len(), 14);
-        assert_eq!(solution.contains(&Point::new(10, 10)), true);
-    }
-
-    #[test]
-    fn test_solution2() {
-        let input = r"T.........
-...T......
-.T........
-..........
-..........
-..........
-..........
-..........
-..........
-..........";
-
-        let data = parse_data(&input);
-        assert_eq!(data.len(), 1);
-        assert_eq!(data.get(&'T').is_some_and(|d| { d.len() == 3 }), true);
-
-        let solution = solve2(&input);
-        assert_eq!(solution.len(), 9);
     }
 }
@@ -0,0 +1,416 @@
+//! Settings shared by every day and command.
+//!
+//! Defaults are overridden by `aoc.toml` in the working directory, which in
+//! turn is overridden by `AOC_YEAR`, `AOC_SESSION`, `AOC_INPUT_DIR`,
+//! `AOC_FORMAT` and `AOC_SERVER`:
+//!
+//! ```toml
+//! year = 2024
+//! cookie_file = "./examples/cookie"  # or `session = "<cookie value>"`
+//! input_dir = "inputs"
+//! format = "text"                     # text, json or csv
+//! server = "https://adventofcode.com" # or a local stand-in
+//! ```
+//!
+//! Without any of them the session cookie is read from `aoc/session` in the
+//! user's config directory, where the `auth` command stores it, or from
+//! `./examples/cookie` of older checkouts while that file does not exist.
+
+use std::{
+    env,
+    error::Error,
+    fmt, fs, io,
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    input::{Cache, InputError},
+    report::Format,
+};
+
+pub const CONFIG_FILE: &str = "aoc.toml";
+pub const DEFAULT_YEAR: i32 = 2024;
+/// Years accepted from the config, the environment and `--year`.
+pub const YEARS: RangeInclusive<i32> = 2015..=2100;
+pub const DEFAULT_SERVER: &str = "https://adventofcode.com";
+const LEGACY_COOKIE_FILE: &str = "./examples/cookie";
+
+/// Where `auth set` stores the session cookie: `aoc/session` in the config
+/// directory of the OS (`~/.config`, `~/Library/Application Support`,
+/// `%APPDATA%`), or the working directory when there is none.
+pub fn session_file() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("aoc")
+        .join("session")
+}
+
+// The stored session, or the cookie file the README used to ask for.
+fn default_session() -> Session {
+    let file = session_file();
+    let legacy = Path::new(LEGACY_COOKIE_FILE);
+    if !file.exists() && legacy.exists() {
+        Session::File(legacy.to_path_buf())
+    } else {
+        Session::File(file)
+    }
+}
+
+/// `value` as a year, if it is one of [`YEARS`].
+pub fn year(value: i64) -> Result<i32, String> {
+    i32::try_from(value)
+        .ok()
+        .filter(|year| YEARS.contains(year))
+        .ok_or_else(|| {
+            format!(
+                "{} is not a year between {} and {}",
+                value,
+                YEARS.start(),
+                YEARS.end()
+            )
+        })
+}
+
+pub fn parse_year(text: &str) -> Result<i32, String> {
+    text.trim()
+        .parse::<i64>()
+        .map_err(|_| format!("'{}' is not a year", text))
+        .and_then(year)
+}
+
+/// The cookie value without a `session=` prefix, if it looks like one.
+pub fn parse_session(cookie: &str) -> Result<String, String> {
+    let cookie = cookie.trim();
+    let cookie = cookie.strip_prefix("session=").unwrap_or(cookie);
+
+    if cookie.is_empty() {
+        return Err("cookie is empty".to_string());
+    }
+    if let Some(c) = cookie.chars().find(|c| !c.is_ascii_hexdigit()) {
+        return Err(format!(
+            "unexpected character '{}', a session cookie is hexadecimal",
+            c
+        ));
+    }
+    if cookie.len() < 32 {
+        return Err(format!(
+            "cookie is {} characters long, expected at least 32",
+            cookie.len()
+        ));
+    }
+
+    Ok(cookie.to_string())
+}
+
+/// Where the AoC session cookie comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Session {
+    Value(String),
+    File(PathBuf),
+}
+
+impl Session {
+    /// The validated cookie, read from its file if needed.
+    pub fn read(&self) -> Result<String, InputError> {
+        let cookie = match self {
+            Session::Value(value) => value.clone(),
+            Session::File(path) => match fs::read_to_string(path) {
+                Ok(cookie) => cookie,
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                    return Err(InputError::NoSession {
+                        source: self.to_string(),
+                    })
+                }
+                Err(error) => {
+                    return Err(InputError::Io {
+                        path: path.clone(),
+                        error,
+                    })
+                }
+            },
+        };
+
+        parse_session(&cookie).map_err(|reason| InputError::InvalidSession {
+            source: self.to_string(),
+            reason,
+        })
+    }
+}
+
+impl fmt::Display for Session {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Session::Value(_) => write!(f, "AOC_SESSION or aoc.toml"),
+            Session::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    pub year: i32,
+    pub session: Session,
+    pub input_dir: PathBuf,
+    pub format: Format,
+    pub server: String,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            year: DEFAULT_YEAR,
+            session: default_session(),
+            input_dir: PathBuf::from("inputs"),
+            format: Format::Text,
+            server: DEFAULT_SERVER.to_string(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io { path: PathBuf, error: io::Error },
+    Invalid { source: String, message: String },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Io { path, error } => write!(f, "{}: {}", path.display(), error),
+            ConfigError::Invalid { source, message } => {
+                write!(f, "invalid configuration in {}: {}", source, message)
+            }
+        }
+    }
+}
+
+impl Error for ConfigError {}
+
+impl Config {
+    /// `aoc.toml` from the working directory with overrides from the environment.
+    pub fn load() -> Result<Self, ConfigError> {
+        Config::load_from(Path::new(CONFIG_FILE), |name| env::var(name).ok())
+    }
+
+    pub fn load_from(
+        path: &Path,
+        var: impl Fn(&str) -> Option<String>,
+    ) -> Result<Self, ConfigError> {
+        let mut config = Config::default();
+
+        match fs::read_to_string(path) {
+            Ok(text) => config.apply_toml(&text, &path.display().to_string())?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(error) => {
+                return Err(ConfigError::Io {
+                    path: path.to_path_buf(),
+                    error,
+                })
+            }
+        }
+        config.apply_env(var)?;
+
+        Ok(config)
+    }
+
+    fn apply_toml(&mut self, text: &str, source: &str) -> Result<(), ConfigError> {
+        let invalid = |message: String| ConfigError::Invalid {
+            source: source.to_string(),
+            message,
+        };
+        let table = text
+            .parse::<toml::Table>()
+            .map_err(|e| invalid(e.message().to_string()))?;
+
+        let string = |key: &str| match table.get(key) {
+            None => Ok(None),
+            Some(toml::Value::String(value)) => Ok(Some(value.clone())),
+            Some(_) => Err(invalid(format!("'{}' has to be a string", key))),
+        };
+
+        if let Some(key) = table.keys().find(|key| {
+            ![
+                "year",
+                "session",
+                "cookie_file",
+                "input_dir",
+                "format",
+                "server",
+            ]
+            .contains(&key.as_str())
+        }) {
+            return Err(invalid(format!("unknown key '{}'", key)));
+        }
+
+        match table.get("year") {
+            None => {}
+            Some(toml::Value::Integer(value)) => self.year = year(*value).map_err(invalid)?,
+            Some(_) => return Err(invalid("'year' has to be a number".to_string())),
+        }
+        // an explicit session value wins over a cookie file
+        if let Some(path) = string("cookie_file")? {
+            self.session = Session::File(PathBuf::from(path));
+        }
+        if let Some(value) = string("session")? {
+            self.session = Session::Value(value);
+        }
+        if let Some(dir) = string("input_dir")? {
+            self.input_dir = PathBuf::from(dir);
+        }
+        if let Some(format) = string("format")? {
+            self.format = format.parse().map_err(invalid)?;
+        }
+        if let Some(server) = string("server")? {
+            self.server = server;
+        }
+
+        Ok(())
+    }
+
+    fn apply_env(&mut self, var: impl Fn(&str) -> Option<String>) -> Result<(), ConfigError> {
+        let invalid = |name: &str, message: String| ConfigError::Invalid {
+            source: name.to_string(),
+            message,
+        };
+
+        if let Some(year) = var("AOC_YEAR") {
+            self.year = parse_year(&year).map_err(|e| invalid("AOC_YEAR", e))?;
+        }
+        if let Some(session) = var("AOC_SESSION").filter(|s| !s.trim().is_empty()) {
+            self.session = Session::Value(session);
+        }
+        if let Some(dir) = var("AOC_INPUT_DIR") {
+            self.input_dir = PathBuf::from(dir);
+        }
+        if let Some(format) = var("AOC_FORMAT") {
+            self.format = format.parse().map_err(|e| invalid("AOC_FORMAT", e))?;
+        }
+        if let Some(server) = var("AOC_SERVER") {
+            self.server = server;
+        }
+
+        Ok(())
+    }
+
+    /// Input cache of the configured year and directory.
+    pub fn cache(&self) -> Cache {
+        Cache::new(self.year, &self.input_dir, self.session.clone()).with_server(&self.server)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, fs, path::PathBuf};
+
+    use super::{parse_session, Config, Session};
+    use crate::{input::InputError, report::Format};
+
+    const COOKIE: &str = "53616c7465645f5f0123456789abcdef0123456789abcdef";
+
+    // every test has its own file, tests run in parallel
+    fn load(name: &str, toml: &str, vars: &[(&str, &str)]) -> Result<Config, String> {
+        let path = std::env::temp_dir().join(format!(
+            "aoc-2024-config-{}-{}.toml",
+            name,
+            std::process::id()
+        ));
+        fs::write(&path, toml).unwrap();
+        let vars = vars.iter().copied().collect::<HashMap<_, _>>();
+        let config = Config::load_from(&path, |name| vars.get(name).map(|v| v.to_string()));
+        fs::remove_file(&path).unwrap();
+        config.map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn test_defaults() {
+        let config = Config::load_from(&PathBuf::from("/nonexistent/aoc.toml"), |_| None).unwrap();
+        assert_eq!(config, Config::default());
+        assert_eq!(
+            config.cache().path(3),
+            PathBuf::from("inputs/2024/day03.txt")
+        );
+    }
+
+    #[test]
+    fn test_file_and_overrides() {
+        let toml =
+            "year = 2023\ncookie_file = \"cookie\"\ninput_dir = \"data\"\nformat = \"json\"\n";
+
+        let config = load("overrides", toml, &[]).unwrap();
+        assert_eq!(config.year, 2023);
+        assert_eq!(config.session, Session::File(PathBuf::from("cookie")));
+        assert_eq!(config.input_dir, PathBuf::from("data"));
+        assert_eq!(config.format, Format::Json);
+
+        let config = load(
+            "overrides",
+            toml,
+            &[
+                ("AOC_YEAR", "2022"),
+                ("AOC_SESSION", COOKIE),
+                ("AOC_INPUT_DIR", "/tmp/aoc"),
+            ],
+        )
+        .unwrap();
+        assert_eq!(config.year, 2022);
+        assert_eq!(config.session, Session::Value(COOKIE.to_string()));
+        assert_eq!(config.session.read().unwrap(), COOKIE);
+        assert_eq!(
+            config.cache().path(1),
+            PathBuf::from("/tmp/aoc/2022/day01.txt")
+        );
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert!(load("invalid", "year = \"soon\"\n", &[])
+            .unwrap_err()
+            .contains("'year' has to be a number"));
+        assert!(load("invalid", "cookie = \"x\"\n", &[])
+            .unwrap_err()
+            .contains("unknown key 'cookie'"));
+        assert!(load("invalid", "format = \"xml\"\n", &[])
+            .unwrap_err()
+            .contains("unknown format 'xml'"));
+        assert!(load("invalid", "year = 4294967296\n", &[])
+            .unwrap_err()
+            .contains("4294967296 is not a year"));
+        assert!(load("invalid", "year = -1\n", &[])
+            .unwrap_err()
+            .contains("-1 is not a year between 2015 and 2100"));
+        assert!(load("invalid", "year = \n", &[]).is_err());
+        assert!(load("invalid", "", &[("AOC_YEAR", "99999")]).is_err());
+        assert_eq!(
+            load("invalid", "", &[("AOC_YEAR", " 2015 ")]).unwrap().year,
+            2015
+        );
+        assert_eq!(
+            load("invalid", "", &[("AOC_YEAR", "next")]).unwrap_err(),
+            "invalid configuration in AOC_YEAR: 'next' is not a year"
+        );
+    }
+
+    #[test]
+    fn test_session() {
+        assert_eq!(
+            parse_session(&format!("session={}\n", COOKIE)).unwrap(),
+            COOKIE
+        );
+        assert!(parse_session("").unwrap_err().contains("empty"));
+        assert!(parse_session("<paste your cookie here>")
+            .unwrap_err()
+            .contains("unexpected character '<'"));
+        assert!(parse_session("53616c746564")
+            .unwrap_err()
+            .contains("expected at least 32"));
+
+        let missing = Session::File("/nonexistent/session".into());
+        assert!(matches!(missing.read(), Err(InputError::NoSession { .. })));
+        let placeholder = Session::Value("your cookie".to_string());
+        assert!(matches!(
+            placeholder.read(),
+            Err(InputError::InvalidSession { .. })
+        ));
+    }
+}
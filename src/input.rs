@@ -0,0 +1,384 @@
+//! Puzzle inputs cached in `inputs/<year>/dayNN.txt`.
+//!
+//! Every cached file has its SHA-256 recorded in `inputs/<year>/SHA256SUMS`
+//! (same format as `sha256sum`), a copy is only trusted when it still matches.
+//! The network is used only for days without a valid copy.
+
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    fmt, fs, io,
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+};
+
+use sha2::{Digest, Sha256};
+
+use crate::config::{Config, Session, DEFAULT_SERVER};
+
+pub const DAYS: RangeInclusive<u32> = 1..=25;
+const CHECKSUMS: &str = "SHA256SUMS";
+
+#[derive(Debug)]
+pub enum InputError {
+    Io { path: PathBuf, error: io::Error },
+    NoSession { source: String },
+    InvalidSession { source: String, reason: String },
+    // the server refused the cookie
+    Unauthorized { day: u32 },
+    Download { day: u32, message: String },
+    ErrorPage { day: u32 },
+    Truncated { day: u32, reason: String },
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::Io { path, error } => write!(f, "{}: {}", path.display(), error),
+            InputError::NoSession { source } => write!(
+                f,
+                "no session cookie in {}, run `cargo run -- auth set` or set AOC_SESSION",
+                source
+            ),
+            InputError::InvalidSession { source, reason } => {
+                write!(f, "invalid session cookie in {}: {}", source, reason)
+            }
+            InputError::Unauthorized { day } => write!(
+                f,
+                "day {} - session cookie expired or unauthorized, \
+                 run `cargo run -- auth set` with a fresh one",
+                day
+            ),
+            InputError::Download { day, message } => {
+                write!(f, "day {} - download failed: {}", day, message)
+            }
+            InputError::ErrorPage { day } => write!(
+                f,
+                "day {} - got an error page instead of the input, check your cookie",
+                day
+            ),
+            InputError::Truncated { day, reason } => {
+                write!(f, "day {} - input looks truncated: {}", day, reason)
+            }
+        }
+    }
+}
+
+impl Error for InputError {}
+
+/// What [`Cache::fetch`] had to do to get an input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fetched {
+    Cached,
+    Downloaded,
+}
+
+pub struct Cache {
+    pub year: i32,
+    pub dir: PathBuf,
+    session: Session,
+    server: String,
+}
+
+impl Cache {
+    pub fn new(year: i32, root: impl AsRef<Path>, session: Session) -> Self {
+        Cache {
+            year,
+            dir: root.as_ref().join(year.to_string()),
+            session,
+            server: DEFAULT_SERVER.to_string(),
+        }
+    }
+
+    /// Downloads from `server` instead of adventofcode.com, e.g. a local stand-in.
+    pub fn with_server(mut self, server: &str) -> Self {
+        self.server = server.trim_end_matches('/').to_string();
+        self
+    }
+
+    pub fn path(&self, day: u32) -> PathBuf {
+        self.dir.join(format!("day{:02}.txt", day))
+    }
+
+    /// Cached input of `day`, `None` when missing or not matching its checksum.
+    pub fn cached(&self, day: u32) -> Result<Option<String>, InputError> {
+        let path = self.path(day);
+        let checksums = self.checksums()?;
+        let Some(expected) = checksums.get(&file_name(&path)) else {
+            return Ok(None);
+        };
+
+        match fs::read_to_string(&path) {
+            Ok(input) if sha256(&input) == *expected => Ok(Some(input)),
+            Ok(_) => Ok(None),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(InputError::Io { path, error }),
+        }
+    }
+
+    /// Input of `day`, downloaded with `download` only when there is no valid copy.
+    pub fn fetch_with(
+        &self,
+        day: u32,
+        download: impl FnOnce(i32, u32) -> Result<String, InputError>,
+    ) -> Result<(String, Fetched), InputError> {
+        if let Some(input) = self.cached(day)? {
+            return Ok((input, Fetched::Cached));
+        }
+
+        let input = download(self.year, day)?;
+        validate(day, &input)?;
+        self.store(day, &input)?;
+
+        Ok((input, Fetched::Downloaded))
+    }
+
+    pub fn fetch(&self, day: u32) -> Result<(String, Fetched), InputError> {
+        // the cookie is only needed, and read, when something is downloaded
+        self.fetch_with(day, |year, day| {
+            download(&self.server, year, day, &self.session.read()?)
+        })
+    }
+
+    /// Asks the server for the first input of the year, to find out whether
+    /// it accepts the cookie. Nothing is cached.
+    pub fn check_session(&self) -> Result<(), InputError> {
+        let cookie = self.session.read()?;
+        let input = download(&self.server, self.year, 1, &cookie)?;
+        validate(1, &input)
+    }
+
+    fn store(&self, day: u32, input: &str) -> Result<(), InputError> {
+        let io_error = |path: &Path| {
+            let path = path.to_path_buf();
+            move |error| InputError::Io { path, error }
+        };
+
+        fs::create_dir_all(&self.dir).map_err(io_error(&self.dir))?;
+        let path = self.path(day);
+        fs::write(&path, input).map_err(io_error(&path))?;
+
+        let mut checksums = self.checksums()?;
+        checksums.insert(file_name(&path), sha256(input));
+        let contents: String = checksums
+            .iter()
+            .map(|(file, hash)| format!("{}  {}\n", hash, file))
+            .collect();
+        let path = self.dir.join(CHECKSUMS);
+        fs::write(&path, contents).map_err(io_error(&path))
+    }
+
+    fn checksums(&self) -> Result<BTreeMap<String, String>, InputError> {
+        let path = self.dir.join(CHECKSUMS);
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(error) => return Err(InputError::Io { path, error }),
+        };
+
+        Ok(contents
+            .lines()
+            .filter_map(|line| line.split_once("  "))
+            .map(|(hash, file)| (file.to_string(), hash.to_string()))
+            .collect())
+    }
+}
+
+/// Input of `day` for the configured year, from the cache or downloaded once.
+pub fn load(config: &Config, day: u32) -> Result<String, InputError> {
+    config.cache().fetch(day).map(|(input, _)| input)
+}
+
+/// Configuration and input for a single day of `year`, as the examples need
+/// them. The year is fixed because an example only solves its own calendar.
+pub fn load_day(year: i32, day: u32) -> Result<(Config, String), Box<dyn Error>> {
+    let config = Config {
+        year,
+        ..Config::load()?
+    };
+    let input = load(&config, day)?;
+    Ok((config, input))
+}
+
+pub fn sha256(input: &str) -> String {
+    Sha256::digest(input.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// Rejects responses that are obviously not a puzzle input.
+pub fn validate(day: u32, input: &str) -> Result<(), InputError> {
+    let start = input.trim_start().to_ascii_lowercase();
+    if start.starts_with("please log in") || start.contains("please log in to get your") {
+        return Err(InputError::Unauthorized { day });
+    }
+    if start.starts_with("<!doctype")
+        || start.starts_with("<html")
+        || start.starts_with("please don't repeatedly request")
+    {
+        return Err(InputError::ErrorPage { day });
+    }
+    if input.trim().is_empty() {
+        return Err(InputError::Truncated {
+            day,
+            reason: "input is empty".to_string(),
+        });
+    }
+    if !input.ends_with('\n') {
+        return Err(InputError::Truncated {
+            day,
+            reason: "missing final newline".to_string(),
+        });
+    }
+
+    Ok(())
+}
+
+fn download(server: &str, year: i32, day: u32, cookie: &str) -> Result<String, InputError> {
+    let url = format!("{}/{}/day/{}/input", server, year, day);
+    let failed = |message: String| InputError::Download { day, message };
+
+    // adventofcode.com answers a missing or expired cookie with a 400
+    let response = match ureq::get(&url)
+        .set("Cookie", &format!("session={}", cookie))
+        .set("User-Agent", "github.com/aoc-2024 input cache")
+        .call()
+    {
+        Ok(response) => response,
+        Err(ureq::Error::Status(400 | 401 | 403, _)) => {
+            return Err(InputError::Unauthorized { day })
+        }
+        Err(e) => return Err(failed(e.to_string())),
+    };
+    let expected = response
+        .header("Content-Length")
+        .and_then(|length| length.parse::<usize>().ok());
+    let input = response.into_string().map_err(|e| failed(e.to_string()))?;
+
+    match expected {
+        Some(length) if length != input.len() => Err(InputError::Truncated {
+            day,
+            reason: format!("got {} of {} bytes", input.len(), length),
+        }),
+        _ => Ok(input),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{Read, Write},
+        net::TcpListener,
+        thread,
+    };
+
+    use super::*;
+
+    fn temp_cache(name: &str) -> Cache {
+        let root = std::env::temp_dir().join(format!("aoc-2024-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        Cache::new(2024, root, Session::File(PathBuf::from("/nonexistent")))
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(validate(1, "3   4\n4   3\n").is_ok());
+        assert!(matches!(
+            validate(1, "<!DOCTYPE html>\n<html>500</html>\n"),
+            Err(InputError::ErrorPage { day: 1 })
+        ));
+        assert!(matches!(
+            validate(
+                1,
+                "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n"
+            ),
+            Err(InputError::Unauthorized { day: 1 })
+        ));
+        assert!(matches!(
+            validate(1, "3   4\n4   "),
+            Err(InputError::Truncated { .. })
+        ));
+        assert!(matches!(validate(1, ""), Err(InputError::Truncated { .. })));
+    }
+
+    #[test]
+    fn test_cache() {
+        let cache = temp_cache("cache");
+        let input = "3   4\n4   3\n";
+
+        let fetched = cache.fetch_with(1, |_, _| Ok(input.to_string())).unwrap();
+        assert_eq!(fetched, (input.to_string(), Fetched::Downloaded));
+        let checksums = fs::read_to_string(cache.dir.join(CHECKSUMS)).unwrap();
+        assert_eq!(checksums, format!("{}  day01.txt\n", sha256(input)));
+
+        // a valid copy never reaches the downloader
+        let fetched = cache.fetch_with(1, |_, _| panic!("network used")).unwrap();
+        assert_eq!(fetched.1, Fetched::Cached);
+
+        // an edited copy no longer matches its checksum
+        fs::write(cache.path(1), "3   4\n").unwrap();
+        assert_eq!(cache.cached(1).unwrap(), None);
+
+        // bad downloads are not stored
+        let error = cache.fetch_with(2, |_, _| Ok("<html></html>".to_string()));
+        assert!(matches!(error, Err(InputError::ErrorPage { day: 2 })));
+        assert!(!cache.path(2).exists());
+
+        fs::remove_dir_all(cache.dir.parent().unwrap()).unwrap();
+    }
+
+    /// Answers a single request with `status` and `body`, returns the server URL.
+    fn stand_in(status: &'static str, body: &'static str) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let server = format!("http://{}", listener.local_addr().unwrap());
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = [0; 4096];
+            let _ = stream.read(&mut request);
+            let response = format!(
+                "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            stream.write_all(response.as_bytes()).unwrap();
+        });
+        server
+    }
+
+    #[test]
+    fn test_session_check() {
+        let cookie = Session::Value("0123456789abcdef".repeat(8));
+        let cache =
+            |server: &str| Cache::new(2024, "/nonexistent", cookie.clone()).with_server(server);
+
+        assert!(cache(&stand_in("200 OK", "3   4\n"))
+            .check_session()
+            .is_ok());
+        assert!(matches!(
+            cache(&stand_in(
+                "400 Bad Request",
+                "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n"
+            ))
+            .check_session(),
+            Err(InputError::Unauthorized { day: 1 })
+        ));
+        assert!(matches!(
+            cache(&stand_in("500 Internal Server Error", "")).check_session(),
+            Err(InputError::Download { day: 1, .. })
+        ));
+
+        let placeholder = Cache::new(2024, "/nonexistent", Session::Value("cookie".to_string()));
+        assert!(matches!(
+            placeholder.check_session(),
+            Err(InputError::InvalidSession { .. })
+        ));
+    }
+}
@@ -0,0 +1,9 @@
+pub mod config;
+pub mod input;
+pub mod parallel;
+pub mod parse;
+pub mod report;
+pub mod runner;
+pub mod solver;
+pub mod watch;
+pub mod years;
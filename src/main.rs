@@ -1,5 +1,235 @@
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    println!("Aoc 2024 - Solutions");
+use std::{
+    env,
+    error::Error,
+    fs,
+    io::{self, Write},
+    thread,
+    time::Instant,
+};
 
-    Ok(())
+use aoc_2024::{
+    config::{self, Config, Session},
+    input::{self, Fetched, DAYS},
+    report::{self, Answers, Format, Status},
+    runner::{self, Report, Task},
+    solver::Solver,
+    watch, years,
+};
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let mut args = env::args().skip(1).collect::<Vec<_>>();
+    let mut config = Config::load().map_err(|e| e.to_string())?;
+
+    // `--year` applies to every command
+    if let Some(i) = args.iter().position(|a| a == "--year") {
+        let year = args.get(i + 1).map_or("", String::as_str);
+        config.year = config::parse_year(year)?;
+        args.drain(i..(i + 2).min(args.len()));
+    }
+
+    match args.first().map(String::as_str) {
+        Some("auth") => auth(&config, &args[1..]),
+        Some("fetch") => fetch(&config, args.get(1).map_or("all", String::as_str)),
+        Some("run") => run(&config, &args[1..]),
+        Some("watch") => {
+            let day = parse_day(args.get(1).map_or("", String::as_str))?;
+            solver(&config, day)?;
+            watch::watch(&config, day);
+            Ok(())
+        }
+        Some(command) => Err(format!("unknown command '{}'", command).into()),
+        None => run(&config, &[]),
+    }
+}
+
+fn parse_day(day: &str) -> Result<u32, Box<dyn Error>> {
+    match day.parse() {
+        Ok(day) if DAYS.contains(&day) => Ok(day),
+        _ => Err(format!("expected a day between 1 and 25, got '{}'", day).into()),
+    }
+}
+
+fn solver(config: &Config, day: u32) -> Result<&'static dyn Solver, Box<dyn Error>> {
+    years::get(config.year, day)
+        .ok_or_else(|| format!("day {} of {} is not solved yet", day, config.year).into())
+}
+
+/// Stores the session cookie (`auth set [cookie]`, read from stdin when not
+/// given) or checks that the server accepts the current one (`auth check`).
+fn auth(config: &Config, args: &[String]) -> Result<(), Box<dyn Error>> {
+    let check = |session: Session| -> Result<(), Box<dyn Error>> {
+        let config = Config {
+            session,
+            ..config.clone()
+        };
+        config.cache().check_session().map_err(|e| e.to_string())?;
+        Ok(())
+    };
+
+    match args.first().map(String::as_str) {
+        Some("set") => {
+            let cookie = match args.get(1) {
+                Some(cookie) => cookie.clone(),
+                None => {
+                    eprintln!("paste the value of your adventofcode.com `session` cookie:");
+                    let mut line = String::new();
+                    io::stdin().read_line(&mut line)?;
+                    line
+                }
+            };
+            let cookie =
+                config::parse_session(&cookie).map_err(|e| format!("invalid cookie: {}", e))?;
+            // a cookie the server refuses never replaces the stored one
+            check(Session::Value(cookie.clone()))?;
+
+            let path = config::session_file();
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            // the secret is never readable by others, not even briefly
+            let mut options = fs::OpenOptions::new();
+            options.write(true).create(true).truncate(true);
+            #[cfg(unix)]
+            std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+            let mut file = options.open(&path)?;
+            // an existing file keeps its mode when opened
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                file.set_permissions(fs::Permissions::from_mode(0o600))?;
+            }
+            writeln!(file, "{}", cookie)?;
+            println!(
+                "session accepted by {} and saved to {}",
+                config.server,
+                path.display()
+            );
+
+            let session = Config::load()?.session;
+            if session != Session::File(path) {
+                eprintln!(
+                    "warning: the cookie from {} is used instead while it is set",
+                    session
+                );
+            }
+            Ok(())
+        }
+        Some("check") | None => {
+            check(config.session.clone())?;
+            println!(
+                "session from {} is accepted by {}",
+                config.session, config.server
+            );
+            Ok(())
+        }
+        Some(command) => Err(format!("unknown auth command '{}'", command).into()),
+    }
+}
+
+/// Downloads inputs missing from the cache, `which` is a day or `all`.
+fn fetch(config: &Config, which: &str) -> Result<(), Box<dyn Error>> {
+    let days = match which {
+        "all" => DAYS.collect::<Vec<_>>(),
+        day => vec![parse_day(day)?],
+    };
+
+    let cache = config.cache();
+    let mut failed = 0;
+    for day in days {
+        match cache.fetch(day) {
+            Ok((_, Fetched::Cached)) => println!("day {:02} - cached", day),
+            Ok((_, Fetched::Downloaded)) => println!("day {:02} - downloaded", day),
+            Err(e) => {
+                eprintln!("{}", e);
+                failed += 1;
+            }
+        }
+    }
+
+    match failed {
+        0 => Ok(()),
+        n => Err(format!("{} input(s) could not be fetched", n).into()),
+    }
+}
+
+/// Solves the given days (all of them by default) on `--jobs` threads.
+fn run(config: &Config, args: &[String]) -> Result<(), Box<dyn Error>> {
+    let option = |name: &str| args.iter().skip_while(|a| *a != name).nth(1);
+    let jobs = match option("--jobs") {
+        Some(jobs) => jobs
+            .parse::<usize>()
+            .map_err(|_| format!("expected a number of jobs, got '{}'", jobs))?,
+        None => thread::available_parallelism().map_or(1, |n| n.get()),
+    };
+    let format = match option("--format") {
+        Some(format) => format.parse::<Format>()?,
+        None => config.format,
+    };
+
+    let mut solvers = vec![];
+    let mut options = args.iter();
+    while let Some(arg) = options.next() {
+        match arg.as_str() {
+            "--jobs" | "--format" => {
+                options.next();
+            }
+            "--record" => {}
+            day => solvers.push(solver(config, parse_day(day)?)?),
+        }
+    }
+    if solvers.is_empty() {
+        solvers = years::solvers(config.year).to_vec();
+    }
+    if solvers.is_empty() {
+        return Err(format!("no solutions for {} yet", config.year).into());
+    }
+
+    if format == Format::Text {
+        println!("Aoc {} - Solutions", config.year);
+    }
+
+    // inputs are loaded up front, so downloads never run concurrently
+    let mut tasks = vec![];
+    let mut unloaded = vec![];
+    for solver in solvers {
+        match input::load(config, solver.day()) {
+            Ok(input) => tasks.push(Task { solver, input }),
+            // still reported, so scripts see the day as failed
+            Err(e) => unloaded.push(Report::failed(solver.day(), &e.to_string())),
+        }
+    }
+
+    let start = Instant::now();
+    let mut reports = runner::run(&tasks, jobs);
+    let elapsed = start.elapsed();
+    reports.extend(unloaded);
+    reports.sort_by_key(|r| r.day);
+
+    let cache = config.cache();
+    let mut answers = Answers::load(&cache)?;
+    let records = report::records(&reports, &answers);
+    print!("{}", report::render(&records, format));
+
+    if format == Format::Text {
+        println!(
+            "{} days in {:.2?} with {} job(s)",
+            reports.len(),
+            elapsed,
+            jobs.max(1)
+        );
+    }
+    if args.iter().any(|a| a == "--record") {
+        let recorded = answers.record(&reports);
+        answers.save()?;
+        eprintln!("recorded {} new answer(s)", recorded);
+    }
+
+    let failed = records
+        .iter()
+        .filter(|r| matches!(r.status, Status::Failed | Status::Wrong))
+        .count();
+    match failed {
+        0 => Ok(()),
+        n => Err(format!("{} part(s) failed or gave a wrong answer", n).into()),
+    }
 }
@@ -0,0 +1,78 @@
+//! Sequential or rayon-based iteration, picked by the `parallel` feature.
+//!
+//! Both modes give the same results in the same order, solvers take a
+//! [`Mode`] so tests can run them side by side.
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    Sequential,
+    #[cfg(feature = "parallel")]
+    Parallel,
+}
+
+impl Default for Mode {
+    #[cfg(feature = "parallel")]
+    fn default() -> Self {
+        Mode::Parallel
+    }
+
+    #[cfg(not(feature = "parallel"))]
+    fn default() -> Self {
+        Mode::Sequential
+    }
+}
+
+impl Mode {
+    /// Every mode supported by this build, handy for comparing them.
+    pub fn all() -> Vec<Mode> {
+        vec![
+            Mode::Sequential,
+            #[cfg(feature = "parallel")]
+            Mode::Parallel,
+        ]
+    }
+
+    pub fn map<T, U>(self, items: &[T], f: impl Fn(&T) -> U + Sync + Send) -> Vec<U>
+    where
+        T: Sync,
+        U: Send,
+    {
+        match self {
+            Mode::Sequential => items.iter().map(f).collect(),
+            #[cfg(feature = "parallel")]
+            Mode::Parallel => items.par_iter().map(f).collect(),
+        }
+    }
+
+    pub fn filter<T>(self, items: &[T], predicate: impl Fn(&T) -> bool + Sync + Send) -> Vec<&T>
+    where
+        T: Sync,
+    {
+        let keep = self.map(items, predicate);
+        items
+            .iter()
+            .zip(keep)
+            .filter_map(|(item, keep)| keep.then_some(item))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Mode;
+
+    #[test]
+    fn test_modes() {
+        let items = (0..1000).collect::<Vec<u32>>();
+
+        for mode in Mode::all() {
+            assert_eq!(mode.map(&items, |i| i * 2)[999], 1998);
+            let odd = mode.filter(&items, |i| i % 2 == 1);
+            assert_eq!(odd.len(), 500);
+            assert!(odd.windows(2).all(|w| w[0] < w[1]));
+        }
+    }
+}
@@ -0,0 +1,339 @@
+//! Small composable parsers shared by all days.
+//!
+//! Everything works on [`Span`], a piece of the input that remembers where
+//! it starts, so any error points to the line and column it came from.
+
+use std::{any::type_name, error::Error, fmt, str::FromStr};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based line of the input
+    pub line: usize,
+    /// 1-based column, counted in characters
+    pub column: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+pub type Result<T> = std::result::Result<T, ParseError>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span<'a> {
+    pub text: &'a str,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl<'a> From<&'a str> for Span<'a> {
+    fn from(text: &'a str) -> Self {
+        Span::new(text)
+    }
+}
+
+impl<'a> Span<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Span {
+            text,
+            line: 1,
+            column: 1,
+        }
+    }
+
+    pub fn error(&self, message: impl Into<String>) -> ParseError {
+        ParseError {
+            line: self.line,
+            column: self.column,
+            message: message.into(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.trim().is_empty()
+    }
+
+    /// Part of this span, `sub` has to be a slice of `self.text`.
+    fn slice(&self, sub: &'a str) -> Span<'a> {
+        let offset = sub.as_ptr() as usize - self.text.as_ptr() as usize;
+        let before = &self.text[..offset];
+        let (line, column) = match before.rfind('\n') {
+            Some(newline) => (
+                self.line + before.matches('\n').count(),
+                before[newline + 1..].chars().count() + 1,
+            ),
+            None => (self.line, self.column + before.chars().count()),
+        };
+
+        Span {
+            text: sub,
+            line,
+            column,
+        }
+    }
+
+    pub fn trim(&self) -> Span<'a> {
+        self.slice(self.text.trim())
+    }
+
+    pub fn lines(&self) -> impl Iterator<Item = Span<'a>> + '_ {
+        self.text
+            .lines()
+            .map(move |line| self.slice(line.trim_end_matches('\r')))
+    }
+
+    pub fn words(&self) -> impl Iterator<Item = Span<'a>> + '_ {
+        self.text
+            .split_whitespace()
+            .map(move |word| self.slice(word))
+    }
+
+    pub fn split(&self, separator: &'a str) -> impl Iterator<Item = Span<'a>> + '_ {
+        self.text
+            .split(separator)
+            .map(move |part| self.slice(part).trim())
+    }
+
+    pub fn split_once(&self, separator: &str) -> Result<(Span<'a>, Span<'a>)> {
+        match self.text.split_once(separator) {
+            Some((left, right)) => Ok((self.slice(left).trim(), self.slice(right).trim())),
+            None => Err(self.error(format!("expected '{}'", separator))),
+        }
+    }
+
+    pub fn parse<T>(&self) -> Result<T>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        let span = self.trim();
+        if span.text.is_empty() {
+            return Err(span.error(format!("expected {}, found nothing", type_name::<T>())));
+        }
+        span.text.parse().map_err(|e| {
+            span.error(format!(
+                "invalid {} '{}': {}",
+                type_name::<T>(),
+                span.text,
+                e
+            ))
+        })
+    }
+}
+
+/// Any integer (or other `FromStr` type), surrounding whitespace is ignored.
+pub fn number<T>(span: Span) -> Result<T>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    span.parse()
+}
+
+/// Whitespace separated values, e.g. `7 6 4 2 1`.
+pub fn words<T>(span: Span) -> Result<Vec<T>>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    span.words().map(|w| w.parse()).collect()
+}
+
+/// Values separated by `separator`, e.g. `75,47,61` with `","`.
+pub fn separated<T>(span: Span, separator: &str) -> Result<Vec<T>>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    span.split(separator).map(|part| part.parse()).collect()
+}
+
+/// Exactly two values separated by `separator`, e.g. `47|53` with `"|"`.
+pub fn pair<T>(span: Span, separator: &str) -> Result<(T, T)>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    let (left, right) = span.split_once(separator)?;
+    if right.text.contains(separator) {
+        return Err(right.error(format!(
+            "expected only two values separated by '{}'",
+            separator
+        )));
+    }
+    Ok((left.parse()?, right.parse()?))
+}
+
+/// Key followed by whitespace separated values, e.g. `190: 10 19` with `":"`.
+pub fn key_values<K, V>(span: Span, separator: &str) -> Result<(K, Vec<V>)>
+where
+    K: FromStr,
+    K::Err: fmt::Display,
+    V: FromStr,
+    V::Err: fmt::Display,
+{
+    let (key, values) = span.split_once(separator)?;
+    Ok((key.parse()?, words(values)?))
+}
+
+/// Applies `parser` to every non-blank line, lines are trimmed.
+pub fn lines<'a, T>(
+    input: impl Into<Span<'a>>,
+    mut parser: impl FnMut(Span<'a>) -> Result<T>,
+) -> Result<Vec<T>> {
+    let input = input.into();
+    input
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| parser(line.trim()))
+        .collect()
+}
+
+/// Groups of lines separated by one or more blank lines.
+pub fn sections<'a>(input: impl Into<Span<'a>>) -> Vec<Span<'a>> {
+    let input = input.into();
+    let mut sections = vec![];
+    let mut current: Option<(Span<'a>, Span<'a>)> = None;
+
+    for line in input.lines() {
+        match (line.is_empty(), current) {
+            (true, Some((first, last))) => {
+                sections.push(join(&input, first, last));
+                current = None;
+            }
+            (true, None) => {}
+            (false, Some((first, _))) => current = Some((first, line)),
+            (false, None) => current = Some((line, line)),
+        }
+    }
+    if let Some((first, last)) = current {
+        sections.push(join(&input, first, last));
+    }
+
+    sections
+}
+
+fn join<'a>(input: &Span<'a>, first: Span<'a>, last: Span<'a>) -> Span<'a> {
+    let start = first.text.as_ptr() as usize - input.text.as_ptr() as usize;
+    let end = last.text.as_ptr() as usize - input.text.as_ptr() as usize + last.text.len();
+    input.slice(&input.text[start..end])
+}
+
+/// Rectangular grid of characters, lines are trimmed and blank lines skipped.
+pub fn char_grid<'a>(input: impl Into<Span<'a>>) -> Result<Vec<Vec<char>>> {
+    let rows = lines(input, |line| {
+        Ok((line, line.text.chars().collect::<Vec<_>>()))
+    })?;
+
+    let Some((_, first)) = rows.first() else {
+        return Err(ParseError {
+            line: 1,
+            column: 1,
+            message: "expected a grid, found nothing".to_string(),
+        });
+    };
+    let width = first.len();
+    if let Some((line, row)) = rows.iter().find(|(_, row)| row.len() != width) {
+        return Err(line.error(format!(
+            "row has {} characters, expected {}",
+            row.len(),
+            width
+        )));
+    }
+
+    Ok(rows.into_iter().map(|(_, row)| row).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_numbers() {
+        assert_eq!(number::<u32>(Span::new(" 42 ")), Ok(42));
+        assert_eq!(number::<i8>(Span::new("-128")), Ok(-128));
+        assert_eq!(
+            number::<u64>(Span::new("18446744073709551615")),
+            Ok(u64::MAX)
+        );
+
+        let error = number::<u8>(Span::new("  256")).unwrap_err();
+        assert_eq!((error.line, error.column), (1, 3));
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 3: invalid u8 '256': number too large to fit in target type"
+        );
+
+        let error = number::<u32>(Span::new("")).unwrap_err();
+        assert_eq!(error.message, "expected u32, found nothing");
+    }
+
+    #[test]
+    fn test_lists() {
+        assert_eq!(
+            words::<u32>(Span::new("7 6  4 2 1")),
+            Ok(vec![7, 6, 4, 2, 1])
+        );
+        assert_eq!(
+            separated::<u32>(Span::new("75,47,61"), ","),
+            Ok(vec![75, 47, 61])
+        );
+        assert_eq!(pair::<u32>(Span::new("47|53"), "|"), Ok((47, 53)));
+
+        let error = separated::<u32>(Span::new("75,4x,61"), ",").unwrap_err();
+        assert_eq!(error.column, 4);
+
+        let error = pair::<u32>(Span::new("47 53"), "|").unwrap_err();
+        assert_eq!(error.message, "expected '|'");
+        assert!(pair::<u32>(Span::new("1|2|3"), "|").is_err());
+    }
+
+    #[test]
+    fn test_key_values() {
+        assert_eq!(
+            key_values::<u64, u64>(Span::new("3267: 81 40 27"), ":"),
+            Ok((3267, vec![81, 40, 27]))
+        );
+
+        let error = key_values::<u64, u64>(Span::new("3267: 81 x 27"), ":").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 10));
+    }
+
+    #[test]
+    fn test_lines_and_sections() {
+        let input = "47|53\n97|13\n\n  \n75,47,61\n97,x\n";
+
+        let parts = sections(input);
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[0].text, "47|53\n97|13");
+        assert_eq!(parts[1].line, 5);
+
+        let rules = lines(parts[0], |l| pair::<u32>(l, "|"));
+        assert_eq!(rules, Ok(vec![(47, 53), (97, 13)]));
+
+        let error = lines(parts[1], |l| separated::<u32>(l, ",")).unwrap_err();
+        assert_eq!((error.line, error.column), (6, 4));
+    }
+
+    #[test]
+    fn test_char_grid() {
+        assert_eq!(
+            char_grid("ab\n  cd\n"),
+            Ok(vec![vec!['a', 'b'], vec!['c', 'd']])
+        );
+
+        let error = char_grid("abc\nab\nabc").unwrap_err();
+        assert_eq!(error.line, 2);
+        assert_eq!(error.message, "row has 2 characters, expected 3");
+        assert!(char_grid("\n").is_err());
+    }
+}
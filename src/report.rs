@@ -0,0 +1,351 @@
+//! Results of a run as records, one per day and part, rendered as text,
+//! JSON or CSV.
+//!
+//! Known answers are kept in `answers.txt` next to the cached inputs, one
+//! `<day> <part> <answer>` per line, so every record can say whether its
+//! answer is still the right one.
+
+use std::{collections::BTreeMap, fmt, fs, io, path::PathBuf, str::FromStr, time::Duration};
+
+use serde_json::json;
+
+use crate::{
+    config::Config,
+    input::Cache,
+    parse,
+    runner::{self, Report, Task},
+    solver::Solver,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Correct,
+    Wrong,
+    Unknown,
+    Failed,
+}
+
+impl Status {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Status::Correct => "correct",
+            Status::Wrong => "wrong",
+            Status::Unknown => "unknown",
+            Status::Failed => "failed",
+        }
+    }
+}
+
+pub struct Answers {
+    path: PathBuf,
+    known: BTreeMap<(u32, u8), String>,
+}
+
+impl Answers {
+    pub fn path(cache: &Cache) -> PathBuf {
+        cache.dir.join("answers.txt")
+    }
+
+    /// Known answers of the cache, none when the file does not exist yet.
+    pub fn load(cache: &Cache) -> io::Result<Self> {
+        let path = Answers::path(cache);
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e),
+        };
+
+        let known = parse::lines(contents.as_str(), |line| {
+            let mut words = line.words();
+            let (Some(day), Some(part)) = (words.next(), words.next()) else {
+                return Err(line.error("expected '<day> <part> <answer>'"));
+            };
+            let answer = words.map(|w| w.text).collect::<Vec<_>>().join(" ");
+            Ok(((day.parse()?, part.parse()?), answer))
+        })
+        .map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{}: {}", path.display(), e),
+            )
+        })?;
+
+        Ok(Answers {
+            path,
+            known: known.into_iter().collect(),
+        })
+    }
+
+    pub fn get(&self, day: u32, part: u8) -> Option<&str> {
+        self.known.get(&(day, part)).map(String::as_str)
+    }
+
+    pub fn status(&self, day: u32, part: u8, answer: &Result<String, String>) -> Status {
+        match (answer, self.get(day, part)) {
+            (Err(_), _) => Status::Failed,
+            (Ok(_), None) => Status::Unknown,
+            (Ok(answer), Some(known)) if answer == known => Status::Correct,
+            (Ok(_), Some(_)) => Status::Wrong,
+        }
+    }
+
+    /// Remembers answers of parts without a known one, returns how many.
+    pub fn record(&mut self, reports: &[Report]) -> usize {
+        let mut recorded = 0;
+        for report in reports {
+            for part in report.parts.iter() {
+                if let Ok(answer) = &part.answer {
+                    if !answer.is_empty() {
+                        self.known
+                            .entry((report.day, part.part))
+                            .or_insert_with(|| {
+                                recorded += 1;
+                                answer.clone()
+                            });
+                    }
+                }
+            }
+        }
+        recorded
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let contents: String = self
+            .known
+            .iter()
+            .map(|((day, part), answer)| format!("{} {} {}\n", day, part, answer))
+            .collect();
+        fs::write(&self.path, contents)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    pub day: u32,
+    pub part: u8,
+    pub answer: Result<String, String>,
+    pub expected: Option<String>,
+    pub status: Status,
+    pub input_hash: String,
+    pub elapsed: Duration,
+}
+
+pub fn records(reports: &[Report], answers: &Answers) -> Vec<Record> {
+    reports
+        .iter()
+        .flat_map(|report| {
+            report.parts.iter().map(|part| Record {
+                day: report.day,
+                part: part.part,
+                answer: part.answer.clone(),
+                expected: answers.get(report.day, part.part).map(String::from),
+                status: answers.status(report.day, part.part, &part.answer),
+                input_hash: report.input_hash.clone(),
+                elapsed: part.elapsed,
+            })
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!(
+                "unknown format '{}', expected text, json or csv",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Record {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "day {} - part {}: ", self.day, self.part)?;
+        match (&self.answer, self.status, &self.expected) {
+            (Err(e), _, _) => write!(f, "error: {}", e),
+            (Ok(answer), Status::Wrong, Some(expected)) => write!(
+                f,
+                "{} ({:.2?}) - wrong, expected {}",
+                answer, self.elapsed, expected
+            ),
+            (Ok(answer), _, _) => write!(f, "{} ({:.2?})", answer, self.elapsed),
+        }
+    }
+}
+
+pub fn render(records: &[Record], format: Format) -> String {
+    match format {
+        Format::Text => records.iter().map(|r| format!("{}\n", r)).collect(),
+        Format::Json => {
+            let records = records
+                .iter()
+                .map(|r| {
+                    json!({
+                        "day": r.day,
+                        "part": r.part,
+                        "answer": r.answer.as_ref().ok(),
+                        "error": r.answer.as_ref().err(),
+                        "expected": r.expected,
+                        "status": r.status.as_str(),
+                        "input_sha256": r.input_hash,
+                        "elapsed_ns": r.elapsed.as_nanos() as u64,
+                    })
+                })
+                .collect::<Vec<_>>();
+            format!("{:#}\n", serde_json::Value::from(records))
+        }
+        Format::Csv => {
+            let mut csv =
+                String::from("day,part,answer,error,expected,status,input_sha256,elapsed_ns\n");
+            for r in records {
+                let fields = [
+                    r.day.to_string(),
+                    r.part.to_string(),
+                    r.answer.clone().unwrap_or_default(),
+                    r.answer.clone().err().unwrap_or_default(),
+                    r.expected.clone().unwrap_or_default(),
+                    r.status.as_str().to_string(),
+                    r.input_hash.clone(),
+                    r.elapsed.as_nanos().to_string(),
+                ];
+                let fields = fields.iter().map(|f| csv_field(f)).collect::<Vec<_>>();
+                csv.push_str(&fields.join(","));
+                csv.push('\n');
+            }
+            csv
+        }
+    }
+}
+
+/// Solves a single day and prints it the way the runner does.
+pub fn print_day(config: &Config, solver: &'static dyn Solver, input: String) {
+    let report = runner::solve(&Task { solver, input });
+    let cache = config.cache();
+    let answers = Answers::load(&cache).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        Answers {
+            path: Answers::path(&cache),
+            known: BTreeMap::new(),
+        }
+    });
+
+    print!("{}", render(&records(&[report], &answers), config.format));
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, time::Duration};
+
+    use super::{records, render, Answers, Format, Status};
+    use crate::{
+        config::Config,
+        runner::{Part, Report},
+    };
+
+    fn report(day: u32, part1: Result<&str, &str>, part2: Result<&str, &str>) -> Report {
+        let part = |part, answer: Result<&str, &str>| Part {
+            part,
+            answer: answer.map(String::from).map_err(String::from),
+            elapsed: Duration::from_micros(1500),
+        };
+        Report {
+            day,
+            input_hash: "ab12".to_string(),
+            parts: [part(1, part1), part(2, part2)],
+        }
+    }
+
+    #[test]
+    fn test_answers() {
+        let root = std::env::temp_dir().join(format!("aoc-2024-answers-{}", std::process::id()));
+        let cache = Config {
+            input_dir: root.clone(),
+            ..Config::default()
+        }
+        .cache();
+        fs::create_dir_all(&cache.dir).unwrap();
+        fs::write(Answers::path(&cache), "1 1 11\n1 2 31\n").unwrap();
+
+        let mut answers = Answers::load(&cache).unwrap();
+        let reports = [
+            report(1, Ok("11"), Ok("30")),
+            report(2, Ok("2"), Err("expected u32")),
+        ];
+        let statuses = records(&reports, &answers)
+            .iter()
+            .map(|r| r.status)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            statuses,
+            [
+                Status::Correct,
+                Status::Wrong,
+                Status::Unknown,
+                Status::Failed
+            ]
+        );
+
+        assert_eq!(answers.record(&reports), 1);
+        answers.save().unwrap();
+        let saved = Answers::load(&cache).unwrap();
+        assert_eq!(saved.get(2, 1), Some("2"));
+        assert_eq!(saved.get(1, 2), Some("31"));
+
+        fs::write(Answers::path(&cache), "1 x 11\n").unwrap();
+        assert!(Answers::load(&cache).is_err());
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_render() {
+        let config = Config {
+            input_dir: "/nonexistent".into(),
+            ..Config::default()
+        };
+        let answers = Answers::load(&config.cache()).unwrap();
+        let records = records(&[report(3, Ok("161"), Err("bad, \"input\""))], &answers);
+
+        assert_eq!(
+            render(&records, Format::Text),
+            "day 3 - part 1: 161 (1.50ms)\nday 3 - part 2: error: bad, \"input\"\n"
+        );
+
+        let csv = render(&records, Format::Csv);
+        let lines = csv.lines().collect::<Vec<_>>();
+        assert_eq!(lines[1], "3,1,161,,,unknown,ab12,1500000");
+        assert_eq!(lines[2], "3,2,,\"bad, \"\"input\"\"\",,failed,ab12,1500000");
+
+        let json: serde_json::Value =
+            serde_json::from_str(&render(&records, Format::Json)).unwrap();
+        assert_eq!(json[0]["answer"], "161");
+        assert_eq!(json[0]["status"], "unknown");
+        assert_eq!(json[1]["answer"], serde_json::Value::Null);
+        assert_eq!(json[1]["error"], "bad, \"input\"");
+        assert_eq!(json[1]["elapsed_ns"], 1500000);
+        assert!("xml".parse::<Format>().is_err());
+    }
+}
@@ -0,0 +1,175 @@
+//! Runs solvers on their inputs, several days at once when asked to, and
+//! collects answers with timings in day order.
+
+use std::{
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    input,
+    solver::{Answer, Solver},
+};
+
+pub struct Task {
+    pub solver: &'static dyn Solver,
+    pub input: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Part {
+    pub part: u8,
+    pub answer: Result<String, String>,
+    pub elapsed: Duration,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    pub day: u32,
+    pub input_hash: String,
+    pub parts: [Part; 2],
+}
+
+impl Report {
+    /// Both parts failed with `error`, for days that could not even start.
+    pub fn failed(day: u32, error: &str) -> Self {
+        let part = |part| Part {
+            part,
+            answer: Err(error.to_string()),
+            elapsed: Duration::ZERO,
+        };
+        Report {
+            day,
+            input_hash: String::new(),
+            parts: [part(1), part(2)],
+        }
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.parts.iter().map(|p| p.elapsed).sum()
+    }
+}
+
+fn solve_part(part: u8, solve: impl FnOnce() -> Answer) -> Part {
+    let start = Instant::now();
+    // a panicking day must not take the other days down with it
+    let answer = match panic::catch_unwind(AssertUnwindSafe(solve)) {
+        Ok(Ok(answer)) => Ok(answer),
+        Ok(Err(e)) => Err(e.to_string()),
+        Err(_) => Err("solver panicked".to_string()),
+    };
+
+    Part {
+        part,
+        answer,
+        elapsed: start.elapsed(),
+    }
+}
+
+pub fn solve(task: &Task) -> Report {
+    let Task { solver, input } = task;
+
+    Report {
+        day: solver.day(),
+        input_hash: input::sha256(input),
+        parts: [
+            solve_part(1, || solver.part1(input)),
+            solve_part(2, || solver.part2(input)),
+        ],
+    }
+}
+
+/// Solves every task on up to `jobs` threads, `jobs` of 1 (or 0) runs them
+/// one after another on the calling thread.
+pub fn run(tasks: &[Task], jobs: usize) -> Vec<Report> {
+    if jobs <= 1 {
+        return tasks.iter().map(solve).collect();
+    }
+
+    let next = AtomicUsize::new(0);
+    let reports = Mutex::new(Vec::with_capacity(tasks.len()));
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(tasks.len()) {
+            scope.spawn(|| {
+                while let Some(task) = tasks.get(next.fetch_add(1, Ordering::Relaxed)) {
+                    let report = solve(task);
+                    reports.lock().unwrap().push(report);
+                }
+            });
+        }
+    });
+
+    let mut reports = reports.into_inner().unwrap();
+    reports.sort_by_key(|r| r.day);
+    reports
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{thread, time::Duration};
+
+    use super::{run, Task};
+    use crate::solver::{Answer, Solver};
+
+    struct Fake(u32);
+
+    impl Solver for Fake {
+        fn day(&self) -> u32 {
+            self.0
+        }
+
+        fn part1(&self, input: &str) -> Answer {
+            // later days finish first when run in parallel
+            thread::sleep(Duration::from_millis(10 * (4 - self.0 as u64)));
+            Ok(input.len().to_string())
+        }
+
+        fn part2(&self, input: &str) -> Answer {
+            match self.0 {
+                2 => Err("no answer".into()),
+                3 => panic!("broken day"),
+                _ => Ok(input.to_uppercase()),
+            }
+        }
+    }
+
+    static FAKES: [Fake; 3] = [Fake(1), Fake(2), Fake(3)];
+
+    #[test]
+    fn test_run() {
+        let tasks = FAKES
+            .iter()
+            .map(|solver| Task {
+                solver,
+                input: "x".repeat(solver.0 as usize),
+            })
+            .collect::<Vec<_>>();
+
+        let sequential = run(&tasks, 1);
+        let parallel = run(&tasks, 3);
+
+        for reports in [sequential, parallel] {
+            assert_eq!(reports.iter().map(|r| r.day).collect::<Vec<_>>(), [1, 2, 3]);
+            let answers = reports
+                .iter()
+                .flat_map(|r| r.parts.iter().map(|p| p.answer.clone()))
+                .collect::<Vec<_>>();
+            assert_eq!(
+                answers,
+                [
+                    Ok("1".to_string()),
+                    Ok("X".to_string()),
+                    Ok("2".to_string()),
+                    Err("no answer".to_string()),
+                    Ok("3".to_string()),
+                    Err("solver panicked".to_string()),
+                ]
+            );
+        }
+    }
+}
@@ -0,0 +1,12 @@
+//! One interface for the solutions of every day, so they can be run together
+//! by the runner.
+
+use std::error::Error;
+
+pub type Answer = Result<String, Box<dyn Error + Send + Sync>>;
+
+pub trait Solver: Sync {
+    fn day(&self) -> u32;
+    fn part1(&self, input: &str) -> Answer;
+    fn part2(&self, input: &str) -> Answer;
+}
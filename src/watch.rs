@@ -0,0 +1,212 @@
+//! Re-runs a day whenever its source or input changes: sample tests first,
+//! the real input only once they pass.
+
+use std::{
+    env, fmt, fs,
+    path::PathBuf,
+    process::Command,
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::config::Config;
+
+const POLL: Duration = Duration::from_millis(500);
+
+/// Files whose changes trigger a new run of `day`.
+pub fn watched_files(config: &Config, day: u32) -> Vec<PathBuf> {
+    vec![
+        PathBuf::from(format!("src/years/y{}/day{:02}.rs", config.year, day)),
+        PathBuf::from(format!("examples/day{:02}.rs", day)),
+        config.cache().path(day),
+    ]
+}
+
+/// Latest modification time among `paths`, missing files are ignored.
+pub fn last_modified(paths: &[PathBuf]) -> Option<SystemTime> {
+    paths
+        .iter()
+        .filter_map(|path| fs::metadata(path).and_then(|m| m.modified()).ok())
+        .max()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Samples {
+    Passed(usize),
+    Failed { passed: usize, failed: Vec<String> },
+    // the tests did not even compile
+    Broken(String),
+}
+
+impl fmt::Display for Samples {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Samples::Passed(passed) => write!(f, "samples ok ({} passed)", passed),
+            Samples::Failed { passed, failed } => write!(
+                f,
+                "samples FAILED ({} passed, {} failed: {})",
+                passed,
+                failed.len(),
+                failed.join(", ")
+            ),
+            Samples::Broken(error) => write!(f, "build FAILED: {}", error),
+        }
+    }
+}
+
+/// Reads the outcome from the output of `cargo test`.
+pub fn summarize_tests(success: bool, stdout: &str, stderr: &str) -> Samples {
+    let mut passed = 0;
+    let mut failed = vec![];
+    let mut ran = false;
+
+    for line in stdout.lines() {
+        // every failing test gets a `---- name stdout ----` section
+        if let Some(name) = line
+            .strip_prefix("---- ")
+            .and_then(|l| l.strip_suffix(" stdout ----"))
+        {
+            failed.push(name.rsplit("::").next().unwrap_or(name).to_string());
+        } else if let Some(result) = line.strip_prefix("test result: ") {
+            ran = true;
+            passed += result
+                .split(['.', ';'])
+                .find_map(|part| part.trim().strip_suffix(" passed"))
+                .and_then(|n| n.parse::<usize>().ok())
+                .unwrap_or(0);
+        }
+    }
+
+    match (ran, success, failed.is_empty()) {
+        (true, true, _) => Samples::Passed(passed),
+        (true, false, false) => Samples::Failed { passed, failed },
+        _ => Samples::Broken(
+            stderr
+                .lines()
+                .find(|l| l.starts_with("error"))
+                .unwrap_or("cargo test failed")
+                .to_string(),
+        ),
+    }
+}
+
+fn cargo() -> Command {
+    Command::new(env::var("CARGO").unwrap_or("cargo".to_string()))
+}
+
+fn run_samples(year: i32, day: u32) -> Samples {
+    let output = cargo()
+        .args(["test", "--quiet", "--lib"])
+        .arg(format!("years::y{}::day{:02}::", year, day))
+        .output();
+
+    match output {
+        Ok(output) => summarize_tests(
+            output.status.success(),
+            &String::from_utf8_lossy(&output.stdout),
+            &String::from_utf8_lossy(&output.stderr),
+        ),
+        Err(e) => Samples::Broken(e.to_string()),
+    }
+}
+
+// Answers of the real input, as printed by the runner.
+fn run_input(year: i32, day: u32) -> String {
+    let output = cargo()
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            "run",
+            &day.to_string(),
+            "--year",
+            &year.to_string(),
+            "--jobs",
+            "1",
+            // the answers are read from text output, whatever is configured
+            "--format",
+            "text",
+        ])
+        .output();
+
+    match output {
+        Ok(output) => {
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let prefix = format!("day {} - ", day);
+            let answers = stdout
+                .lines()
+                .filter_map(|l| l.strip_prefix(&prefix))
+                .collect::<Vec<_>>();
+            if answers.is_empty() {
+                stderr.lines().next().unwrap_or("no answers").to_string()
+            } else {
+                answers.join(", ")
+            }
+        }
+        Err(e) => e.to_string(),
+    }
+}
+
+/// Watches `day` until interrupted, printing one summary line per run.
+pub fn watch(config: &Config, day: u32) {
+    let files = watched_files(config, day);
+    let names = files
+        .iter()
+        .map(|p| p.display().to_string())
+        .collect::<Vec<_>>();
+    println!("watching {}, Ctrl-C to stop", names.join(", "));
+
+    let mut seen = None;
+    let mut run = 0;
+    loop {
+        let modified = last_modified(&files);
+        if run == 0 || modified != seen {
+            seen = modified;
+            run += 1;
+
+            let samples = run_samples(config.year, day);
+            match samples {
+                Samples::Passed(_) => {
+                    println!(
+                        "#{} day {} - {} - {}",
+                        run,
+                        day,
+                        samples,
+                        run_input(config.year, day)
+                    )
+                }
+                _ => println!("#{} day {} - {}", run, day, samples),
+            }
+        }
+        thread::sleep(POLL);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{summarize_tests, Samples};
+
+    #[test]
+    fn test_summarize_tests() {
+        let passed = "\nrunning 3 tests\n...\ntest result: ok. 3 passed; 0 failed; 0 ignored; 0 measured; 54 filtered out; finished in 0.00s\n";
+        assert_eq!(summarize_tests(true, passed, ""), Samples::Passed(3));
+
+        let failed = "\nrunning 3 tests\n.F.\nfailures:\n\n\
+            ---- years::y2024::day07::tests::test_modes stdout ----\n\
+            assertion failed\n\nfailures:\n    years::y2024::day07::tests::test_modes\n\ntest result: FAILED. 2 passed; 1 failed; 0 ignored; 0 measured; 54 filtered out; finished in 0.00s\n";
+        assert_eq!(
+            summarize_tests(false, failed, ""),
+            Samples::Failed {
+                passed: 2,
+                failed: vec!["test_modes".to_string()]
+            }
+        );
+
+        let broken = "error[E0425]: cannot find value `x` in this scope\n";
+        assert_eq!(
+            summarize_tests(false, "", broken),
+            Samples::Broken("error[E0425]: cannot find value `x` in this scope".to_string())
+        );
+    }
+}
@@ -0,0 +1,50 @@
+//! Solutions grouped by calendar, one `yNNNN` module per year, and the
+//! registry finding them by year and day.
+
+use crate::solver::Solver;
+
+pub mod y2024;
+
+/// Every year with solutions, in year order.
+pub const YEARS: &[(i32, &[&dyn Solver])] = &[(2024, y2024::SOLVERS)];
+
+/// Solved days of `year` in day order, none for an unknown year.
+pub fn solvers(year: i32) -> &'static [&'static dyn Solver] {
+    YEARS
+        .iter()
+        .find(|(y, _)| *y == year)
+        .map_or(&[], |(_, solvers)| solvers)
+}
+
+pub fn get(year: i32, day: u32) -> Option<&'static dyn Solver> {
+    solvers(year)
+        .iter()
+        .copied()
+        .find(|solver| solver.day() == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get, solvers, YEARS};
+
+    #[test]
+    fn test_registry() {
+        for (year, solvers) in YEARS {
+            for (i, solver) in solvers.iter().enumerate() {
+                assert_eq!(get(*year, solver.day()).unwrap().day(), solver.day());
+                if i > 0 {
+                    assert!(
+                        solvers[i - 1].day() < solver.day(),
+                        "{} not in day order",
+                        year
+                    );
+                }
+            }
+        }
+
+        assert_eq!(get(2024, 6).unwrap().day(), 6);
+        assert!(get(2024, 25).is_none());
+        assert!(get(2015, 1).is_none());
+        assert!(solvers(2015).is_empty());
+    }
+}
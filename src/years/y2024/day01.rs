@@ -0,0 +1,637 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    error::Error,
+    fmt,
+    io::{self, BufRead},
+    iter::zip,
+    mem,
+};
+
+use crate::{
+    parse::{self, Span},
+    solver::{Answer, Solver},
+};
+
+// Ids below this limit are only counted, which sorts them for free.
+const COUNTING_LIMIT: usize = 1 << 20;
+
+#[derive(Debug, PartialEq)]
+pub enum Lists {
+    // amount of every id in both lists, indexed by id
+    Counted(Vec<u64>, Vec<u64>),
+    Plain(Vec<u32>, Vec<u32>),
+}
+
+impl Lists {
+    fn new() -> Self {
+        Lists::Counted(vec![], vec![])
+    }
+
+    fn push(&mut self, a: u32, b: u32) {
+        if let Lists::Counted(left, right) = self {
+            let max = a.max(b) as usize;
+            if max < COUNTING_LIMIT {
+                if left.len() <= max {
+                    left.resize(max + 1, 0);
+                    right.resize(max + 1, 0);
+                }
+                left[a as usize] += 1;
+                right[b as usize] += 1;
+                return;
+            }
+
+            // order of ids is lost, but neither part depends on it
+            let (left, right) = self.to_vecs();
+            *self = Lists::Plain(left, right);
+        }
+
+        if let Lists::Plain(left, right) = self {
+            left.push(a);
+            right.push(b);
+        }
+    }
+
+    pub fn distance1(&self) -> u64 {
+        match self {
+            Lists::Counted(left, right) => counted_distance1(left, right),
+            Lists::Plain(left, right) => distance1(left, right),
+        }
+    }
+
+    pub fn distance2(&self) -> u64 {
+        match self {
+            Lists::Counted(left, right) => left
+                .iter()
+                .zip(right.iter())
+                .enumerate()
+                .map(|(id, (l, r))| id as u64 * l * r)
+                .sum(),
+            Lists::Plain(left, right) => distance2(left, right),
+        }
+    }
+
+    pub fn breakdown(&self) -> Vec<Similarity> {
+        match self {
+            Lists::Counted(left, right) => left
+                .iter()
+                .zip(right.iter())
+                .enumerate()
+                .filter(|(_, (&l, &r))| l > 0 || r > 0)
+                .map(|(id, (&left, &right))| Similarity::new(id as u32, left, right))
+                .collect(),
+            Lists::Plain(left, right) => similarity_breakdown(left, right),
+        }
+    }
+
+    pub fn to_vecs(&self) -> (Vec<u32>, Vec<u32>) {
+        match self {
+            Lists::Counted(left, right) => {
+                let expand = |counts: &[u64]| {
+                    counts
+                        .iter()
+                        .enumerate()
+                        .flat_map(|(id, &count)| (0..count).map(move |_| id as u32))
+                        .collect::<Vec<_>>()
+                };
+                (expand(left), expand(right))
+            }
+            Lists::Plain(left, right) => (left.clone(), right.clone()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Issue {
+    MissingColumn {
+        line: usize,
+    },
+    ExtraColumns {
+        line: usize,
+        columns: usize,
+    },
+    NotNumeric {
+        line: usize,
+        token: String,
+    },
+    UnequalLengths {
+        left: usize,
+        right: usize,
+    },
+    // fewer than half of the ids in the list are distinct
+    DuplicateHeavy {
+        list: &'static str,
+        distinct: usize,
+        total: usize,
+    },
+}
+
+impl Issue {
+    // errors make the input invalid, the rest are only warnings
+    pub fn is_error(&self) -> bool {
+        !matches!(self, Issue::DuplicateHeavy { .. })
+    }
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Issue::MissingColumn { line } => write!(f, "line {}: missing second column", line),
+            Issue::ExtraColumns { line, columns } => {
+                write!(f, "line {}: {} columns, expected 2", line, columns)
+            }
+            Issue::NotNumeric { line, token } => {
+                write!(f, "line {}: '{}' is not a location id", line, token)
+            }
+            Issue::UnequalLengths { left, right } => write!(
+                f,
+                "lists have different lengths, left {} and right {}",
+                left, right
+            ),
+            Issue::DuplicateHeavy {
+                list,
+                distinct,
+                total,
+            } => write!(
+                f,
+                "{} list has only {} distinct ids out of {}",
+                list, distinct, total
+            ),
+        }
+    }
+}
+
+// Reads line by line, so the input never has to be in memory as a whole.
+// Only lines with two numeric columns make it to the lists, everything
+// else is reported as an issue with its line number.
+pub fn read_lists<R: BufRead>(mut reader: R) -> io::Result<(Lists, Vec<Issue>)> {
+    let mut lists = Lists::new();
+    let mut issues = vec![];
+    let (mut left, mut right) = (0, 0);
+    let mut line = String::new();
+    let mut number = 0;
+
+    while reader.read_line(&mut line)? > 0 {
+        number += 1;
+        let (values, columns) = parse_line(&line, number, &mut issues);
+        match columns {
+            0 => {}
+            1 => issues.push(Issue::MissingColumn { line: number }),
+            2 => {}
+            columns => issues.push(Issue::ExtraColumns {
+                line: number,
+                columns,
+            }),
+        }
+
+        left += values[0].is_some() as usize;
+        right += values[1].is_some() as usize;
+        if let [Some(a), Some(b)] = values {
+            lists.push(a, b);
+        }
+        line.clear();
+    }
+
+    if left != right {
+        issues.push(Issue::UnequalLengths { left, right });
+    }
+    issues.extend(find_duplicates(&lists));
+
+    Ok((lists, issues))
+}
+
+// Values of the first two columns and the amount of columns on the line.
+fn parse_line(line: &str, number: usize, issues: &mut Vec<Issue>) -> ([Option<u32>; 2], usize) {
+    let mut values = [None, None];
+    let mut columns = 0;
+
+    let line = Span {
+        text: line,
+        line: number,
+        column: 1,
+    };
+    for (i, token) in line.words().enumerate() {
+        columns += 1;
+        if i >= values.len() {
+            continue;
+        }
+        match parse::number(token) {
+            Ok(v) => values[i] = Some(v),
+            Err(_) => issues.push(Issue::NotNumeric {
+                line: number,
+                token: token.text.to_string(),
+            }),
+        }
+    }
+
+    (values, columns)
+}
+
+// Distinct ids come straight from the counts or from a sorted copy of a
+// plain list, so large inputs never need a full breakdown.
+fn find_duplicates(lists: &Lists) -> Vec<Issue> {
+    let check = |list: &'static str, (distinct, total): (usize, usize)| {
+        (distinct * 2 < total).then_some(Issue::DuplicateHeavy {
+            list,
+            distinct,
+            total,
+        })
+    };
+    let counted = |counts: &[u64]| {
+        (
+            counts.iter().filter(|&&c| c > 0).count(),
+            counts.iter().sum::<u64>() as usize,
+        )
+    };
+    let plain = |list: &[u32]| {
+        let mut sorted = list.to_vec();
+        radix_sort(&mut sorted);
+        let distinct = sorted.windows(2).filter(|w| w[0] != w[1]).count();
+        (distinct + !sorted.is_empty() as usize, sorted.len())
+    };
+
+    let (left, right) = match lists {
+        Lists::Counted(left, right) => (counted(left), counted(right)),
+        Lists::Plain(left, right) => (plain(left), plain(right)),
+    };
+    [check("left", left), check("right", right)]
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+// LSD radix sort, one byte per pass.
+fn radix_sort(values: &mut Vec<u32>) {
+    let mut buffer = vec![0; values.len()];
+
+    for shift in (0..32).step_by(8) {
+        let mut offsets = [0usize; 257];
+        for v in values.iter() {
+            offsets[((v >> shift) & 0xff) as usize + 1] += 1;
+        }
+        for i in 1..offsets.len() {
+            offsets[i] += offsets[i - 1];
+        }
+        for &v in values.iter() {
+            let digit = ((v >> shift) & 0xff) as usize;
+            buffer[offsets[digit]] = v;
+            offsets[digit] += 1;
+        }
+        mem::swap(values, &mut buffer);
+    }
+}
+
+pub fn distance1(list1: &[u32], list2: &[u32]) -> u64 {
+    let mut list1 = list1.to_vec();
+    let mut list2 = list2.to_vec();
+    radix_sort(&mut list1);
+    radix_sort(&mut list2);
+
+    let sum: u64 = zip(list1, list2).map(|(a, b)| a.abs_diff(b) as u64).sum();
+
+    sum
+}
+
+// Pairs ids of two sorted lists given as counts per id.
+fn counted_distance1(left: &[u64], right: &[u64]) -> u64 {
+    fn ids(counts: &[u64]) -> impl Iterator<Item = (u64, u64)> + '_ {
+        counts
+            .iter()
+            .enumerate()
+            .filter(|(_, &count)| count > 0)
+            .map(|(id, &count)| (id as u64, count))
+    }
+    let mut left_ids = ids(left);
+    let mut right_ids = ids(right);
+
+    let mut sum = 0;
+    let mut a = left_ids.next();
+    let mut b = right_ids.next();
+    while let (Some((left_id, left_count)), Some((right_id, right_count))) = (a, b) {
+        let pairs = left_count.min(right_count);
+        sum += pairs * left_id.abs_diff(right_id);
+        a = if left_count == pairs {
+            left_ids.next()
+        } else {
+            Some((left_id, left_count - pairs))
+        };
+        b = if right_count == pairs {
+            right_ids.next()
+        } else {
+            Some((right_id, right_count - pairs))
+        };
+    }
+
+    sum
+}
+
+pub fn distance2(list1: &[u32], list2: &[u32]) -> u64 {
+    let mut occurences: HashMap<u32, u64> = HashMap::new();
+    list2.iter().for_each(|v| {
+        occurences
+            .entry(*v)
+            .and_modify(|amount| *amount += 1)
+            .or_insert(1);
+    });
+
+    let sum: u64 = list1
+        .iter()
+        .map(|v| *v as u64 * occurences.get(v).unwrap_or(&0))
+        .sum();
+
+    sum
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Similarity {
+    pub id: u32,
+    // occurrences in the left and right list
+    pub left: u64,
+    pub right: u64,
+    pub contribution: u64,
+}
+
+impl Similarity {
+    fn new(id: u32, left: u64, right: u64) -> Self {
+        Similarity {
+            id,
+            left,
+            right,
+            contribution: id as u64 * left * right,
+        }
+    }
+}
+
+// Every id from either list ordered by id, sum of contributions is the part 2 answer.
+fn similarity_breakdown(list1: &[u32], list2: &[u32]) -> Vec<Similarity> {
+    let mut counts: BTreeMap<u32, (u64, u64)> = BTreeMap::new();
+    list1
+        .iter()
+        .for_each(|id| counts.entry(*id).or_default().0 += 1);
+    list2
+        .iter()
+        .for_each(|id| counts.entry(*id).or_default().1 += 1);
+
+    counts
+        .into_iter()
+        .map(|(id, (left, right))| Similarity::new(id, left, right))
+        .collect()
+}
+
+// Biggest contributions first, ties ordered by id.
+pub fn top_contributors(breakdown: &[Similarity], amount: usize) -> Vec<Similarity> {
+    let mut sorted = breakdown.to_vec();
+    sorted.sort_by(|a, b| b.contribution.cmp(&a.contribution).then(a.id.cmp(&b.id)));
+    sorted.truncate(amount);
+    sorted
+}
+
+// Same result as `distance2`, counting runs of equal ids in both sorted lists.
+pub fn merged_distance2(list1: &[u32], list2: &[u32]) -> u64 {
+    let mut list1 = list1.to_vec();
+    let mut list2 = list2.to_vec();
+    radix_sort(&mut list1);
+    radix_sort(&mut list2);
+
+    let mut sum = 0;
+    let (mut i, mut j) = (0, 0);
+    while i < list1.len() && j < list2.len() {
+        let (a, b) = (list1[i], list2[j]);
+        if a < b {
+            i += 1;
+        } else if a > b {
+            j += 1;
+        } else {
+            let left = list1[i..].iter().take_while(|&&v| v == a).count();
+            let right = list2[j..].iter().take_while(|&&v| v == a).count();
+            sum += a as u64 * left as u64 * right as u64;
+            i += left;
+            j += right;
+        }
+    }
+
+    sum
+}
+
+// Lists of a valid input, warnings are ignored but errors fail the part.
+fn checked_lists(input: &str) -> Result<Lists, Box<dyn Error + Send + Sync>> {
+    let (lists, issues) = read_lists(input.as_bytes())?;
+    let errors = issues
+        .iter()
+        .filter(|i| i.is_error())
+        .map(|i| i.to_string())
+        .collect::<Vec<_>>();
+    if !errors.is_empty() {
+        return Err(errors.join(", ").into());
+    }
+    Ok(lists)
+}
+
+pub struct Day01;
+
+impl Solver for Day01 {
+    fn day(&self) -> u32 {
+        1
+    }
+
+    fn part1(&self, input: &str) -> Answer {
+        Ok(checked_lists(input)?.distance1().to_string())
+    }
+
+    fn part2(&self, input: &str) -> Answer {
+        Ok(checked_lists(input)?.distance2().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        distance1, distance2, merged_distance2, radix_sort, read_lists, similarity_breakdown,
+        top_contributors, Day01, Issue, Lists, Similarity,
+    };
+    use crate::solver::Solver;
+
+    #[test]
+    fn test_day1_part1() {
+        let list1 = vec![3, 4, 2, 1, 3, 3];
+        let list2 = vec![4, 3, 5, 3, 9, 3];
+
+        assert_eq!(distance1(&list1, &list2), 11);
+    }
+
+    #[test]
+    fn test_day1_part2() {
+        let list1 = vec![3, 4, 2, 1, 3, 3];
+        let list2 = vec![4, 3, 5, 3, 9, 3];
+
+        assert_eq!(distance2(&list1, &list2), 31);
+    }
+
+    #[test]
+    fn test_read_lists() {
+        let input = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+
+        let (lists, issues) = read_lists(input.as_bytes()).unwrap();
+        assert_eq!(issues, vec![]);
+        assert!(matches!(lists, Lists::Counted(..)));
+        assert_eq!(lists.distance1(), 11);
+        assert_eq!(lists.distance2(), 31);
+    }
+
+    #[test]
+    fn test_read_lists_invalid_lines() {
+        let input = "3   4\n4\n\n2   5 7\nx   3\n1   3\n";
+
+        let (lists, issues) = read_lists(input.as_bytes()).unwrap();
+        assert_eq!(
+            issues,
+            vec![
+                Issue::MissingColumn { line: 2 },
+                Issue::ExtraColumns {
+                    line: 4,
+                    columns: 3
+                },
+                Issue::NotNumeric {
+                    line: 5,
+                    token: "x".to_string()
+                },
+            ]
+        );
+        let mut expected = Lists::new();
+        expected.push(3, 4);
+        expected.push(2, 5);
+        expected.push(1, 3);
+        assert_eq!(lists, expected);
+    }
+
+    #[test]
+    fn test_radix_sort() {
+        let mut values = vec![70000, 3, u32::MAX, 256, 0, 255, 65536, 3];
+        radix_sort(&mut values);
+        assert_eq!(values, vec![0, 3, 3, 255, 256, 65536, 70000, u32::MAX]);
+    }
+
+    #[test]
+    fn test_large_ids() {
+        let mut lists = Lists::new();
+        lists.push(3, 4);
+        lists.push(4, 3);
+        lists.push(u32::MAX, 0);
+        lists.push(u32::MAX, 0);
+        assert!(matches!(lists, Lists::Plain(..)));
+
+        // doesn't fit into u32
+        assert_eq!(lists.distance1(), 2 * u32::MAX as u64);
+
+        let mut counted = Lists::new();
+        let mut plain = Lists::Plain(vec![], vec![]);
+        for (a, b) in [(3, 4), (4, 3), (2, 5), (1, 3), (3, 9), (3, 3), (70, 1)] {
+            counted.push(a, b);
+            plain.push(a, b);
+        }
+        assert!(matches!(counted, Lists::Counted(..)));
+        assert_eq!(counted.distance1(), plain.distance1());
+        assert_eq!(counted.distance2(), plain.distance2());
+    }
+
+    #[test]
+    fn test_similarity_breakdown() {
+        let list1 = vec![3, 4, 2, 1, 3, 3];
+        let list2 = vec![4, 3, 5, 3, 9, 3];
+
+        let breakdown = similarity_breakdown(&list1, &list2);
+        assert_eq!(
+            breakdown,
+            vec![
+                Similarity::new(1, 1, 0),
+                Similarity::new(2, 1, 0),
+                Similarity::new(3, 3, 3),
+                Similarity::new(4, 1, 1),
+                Similarity::new(5, 0, 1),
+                Similarity::new(9, 0, 1),
+            ]
+        );
+        assert_eq!(breakdown[2].contribution, 27);
+        assert_eq!(
+            breakdown.iter().map(|s| s.contribution).sum::<u64>(),
+            distance2(&list1, &list2)
+        );
+
+        let top = top_contributors(&breakdown, 3);
+        assert_eq!(top.iter().map(|s| s.id).collect::<Vec<_>>(), vec![3, 4, 1]);
+
+        let mut lists = Lists::new();
+        list1
+            .iter()
+            .zip(list2.iter())
+            .for_each(|(a, b)| lists.push(*a, *b));
+        assert_eq!(lists.breakdown(), breakdown);
+    }
+
+    #[test]
+    fn test_merged_distance2() {
+        let list1 = vec![3, 4, 2, 1, 3, 3];
+        let list2 = vec![4, 3, 5, 3, 9, 3];
+        assert_eq!(merged_distance2(&list1, &list2), 31);
+
+        let list1 = vec![u32::MAX, 7, 7, 0];
+        let list2 = vec![7, u32::MAX, 1, u32::MAX];
+        assert_eq!(merged_distance2(&list1, &list2), distance2(&list1, &list2));
+    }
+
+    #[test]
+    fn test_validation() {
+        let input = "3   4\n4\n2   5\n1   3\n3\n";
+
+        let (lists, issues) = read_lists(input.as_bytes()).unwrap();
+        assert_eq!(lists.distance1(), 6);
+        assert_eq!(
+            issues,
+            vec![
+                Issue::MissingColumn { line: 2 },
+                Issue::MissingColumn { line: 5 },
+                Issue::UnequalLengths { left: 5, right: 3 },
+            ]
+        );
+        assert!(issues.iter().all(|i| i.is_error()));
+        assert_eq!(
+            issues[2].to_string(),
+            "lists have different lengths, left 5 and right 3"
+        );
+
+        let input = "3   4\n3   3\n3   5\n1   3\n3   9\n";
+        let (_, issues) = read_lists(input.as_bytes()).unwrap();
+        assert_eq!(
+            issues,
+            vec![Issue::DuplicateHeavy {
+                list: "left",
+                distinct: 2,
+                total: 5
+            }]
+        );
+        assert!(!issues[0].is_error());
+        assert_eq!(
+            issues[0].to_string(),
+            "left list has only 2 distinct ids out of 5"
+        );
+
+        assert_eq!(
+            Day01.part1("3   4\n4\n2   x\n").unwrap_err().to_string(),
+            "line 2: missing second column, line 3: 'x' is not a location id, \
+             lists have different lengths, left 3 and right 1"
+        );
+        assert_eq!(Day01.part2("3   4\n3   3\n3   5\n").unwrap(), "9");
+
+        // ids beyond the counting limit take the plain path
+        let input = "9999999   1\n9999999   2\n9999999   3\n5   4\n5   6\n";
+        let (lists, issues) = read_lists(input.as_bytes()).unwrap();
+        assert!(matches!(lists, Lists::Plain(..)));
+        assert_eq!(
+            issues,
+            vec![Issue::DuplicateHeavy {
+                list: "left",
+                distinct: 2,
+                total: 5
+            }]
+        );
+    }
+}
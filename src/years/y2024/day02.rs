@@ -0,0 +1,138 @@
+use crate::{
+    parallel::Mode,
+    parse::{self, ParseError},
+    solver::{Answer, Solver},
+};
+
+pub fn parse_data(input: &str) -> Result<Vec<Vec<u32>>, ParseError> {
+    parse::lines(input, parse::words)
+}
+
+pub fn get_safe_reports(input: &[Vec<u32>]) -> Vec<&Vec<u32>> {
+    check_reports(input, is_report_ok, Mode::default())
+}
+
+fn is_report_ok(report: &[u32]) -> bool {
+    let pairs: Vec<(u32, u32)> = report
+        .iter()
+        .copied()
+        .zip(report.iter().copied().skip(1))
+        .collect();
+
+    // a single level is trivially safe, also when left after removing one
+    let Some((left, right)) = pairs.first() else {
+        return true;
+    };
+    let is_ascending = left < right;
+
+    pairs.iter().all(|(a, b)| {
+        if is_ascending != (a < b) {
+            return false;
+        }
+        matches!(a.abs_diff(*b), 1..=3)
+    })
+}
+
+pub fn get_single_bad_level_reports(input: &[Vec<u32>]) -> Vec<&Vec<u32>> {
+    check_reports(input, is_fixable_report, Mode::default())
+}
+
+// Unsafe report that becomes safe by removing a single level.
+fn is_fixable_report(report: &[u32]) -> bool {
+    if is_report_ok(report) {
+        return false;
+    }
+    (0..report.len()).any(|i| {
+        let mut subreport = report.to_vec();
+        subreport.remove(i);
+        is_report_ok(&subreport)
+    })
+}
+
+fn check_reports(reports: &[Vec<u32>], check: fn(&[u32]) -> bool, mode: Mode) -> Vec<&Vec<u32>> {
+    mode.filter(reports, |report| check(report))
+}
+
+pub struct Day02;
+
+impl Solver for Day02 {
+    fn day(&self) -> u32 {
+        2
+    }
+
+    fn part1(&self, input: &str) -> Answer {
+        let reports = parse_data(input)?;
+        Ok(get_safe_reports(&reports).len().to_string())
+    }
+
+    fn part2(&self, input: &str) -> Answer {
+        let reports = parse_data(input)?;
+        let safe_reports = get_safe_reports(&reports).len();
+        let single_bad_level_reports = get_single_bad_level_reports(&reports).len();
+        Ok((safe_reports + single_bad_level_reports).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        check_reports, get_safe_reports, get_single_bad_level_reports, is_fixable_report,
+        is_report_ok, parse_data,
+    };
+    use crate::parallel::Mode;
+
+    fn sample() -> Vec<Vec<u32>> {
+        vec![
+            vec![7, 6, 4, 2, 1],
+            vec![1, 2, 7, 8, 9],
+            vec![9, 7, 6, 2, 1],
+            vec![1, 3, 2, 4, 5],
+            vec![8, 6, 4, 4, 1],
+            vec![1, 3, 6, 7, 9],
+        ]
+    }
+
+    #[test]
+    fn test_safe_reports() {
+        let reports = sample();
+
+        let safe_reports = get_safe_reports(&reports);
+        let single_bad_level_reports = get_single_bad_level_reports(&reports);
+        assert_eq!(safe_reports.len(), 2);
+        assert_eq!(single_bad_level_reports.len(), 2);
+    }
+
+    #[test]
+    fn test_modes() {
+        let reports = sample();
+        for check in [is_report_ok, is_fixable_report] {
+            let sequential = check_reports(&reports, check, Mode::Sequential);
+            for mode in Mode::all() {
+                assert_eq!(check_reports(&reports, check, mode), sequential);
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_data() {
+        let input = "7 6 4 2 1\n1 2 7 8 9\n";
+        assert_eq!(
+            parse_data(input),
+            Ok(vec![vec![7, 6, 4, 2, 1], vec![1, 2, 7, 8, 9]])
+        );
+
+        let error = parse_data("7 6 4 2 1\n1 2 x 8 9").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 5));
+
+        assert_eq!(parse_data("5\n"), Ok(vec![vec![5]]));
+    }
+
+    #[test]
+    fn test_two_levels() {
+        let reports = vec![vec![1, 9]];
+        assert!(get_safe_reports(&reports).is_empty());
+        assert_eq!(get_single_bad_level_reports(&reports).len(), 1);
+
+        assert_eq!(get_safe_reports(&[vec![5]]).len(), 1);
+    }
+}
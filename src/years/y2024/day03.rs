@@ -0,0 +1,549 @@
+use std::{fmt::Write, ops::Range, time::Instant};
+
+use regex::Regex;
+
+use crate::solver::{Answer, Solver};
+
+fn parse_data(input: &str) -> Vec<(u32, u32)> {
+    let re = Regex::new(r"mul\((\d{1,3}),(\d{1,3})\)").unwrap();
+    let pairs: Vec<(u32, u32)> = re
+        .captures_iter(input)
+        .map(|caps| {
+            let (_, [first, second]) = caps.extract();
+            (first.parse().unwrap(), second.parse().unwrap())
+        })
+        .collect();
+
+    pairs
+}
+
+fn multiply(data: &[(u32, u32)]) -> u32 {
+    data.iter().map(|(first, second)| first * second).sum()
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Instruction<'a> {
+    Mul(u32, u32),
+    Do,
+    Dont,
+    // well-formed call we don't know yet, e.g. `what()` or `from(12,3)`
+    Unknown { name: &'a str, args: &'a str },
+}
+
+#[derive(Debug, PartialEq, Clone)]
+struct Token<'a> {
+    span: Range<usize>,
+    instruction: Instruction<'a>,
+}
+
+struct Tokenizer<'a> {
+    input: &'a str,
+    position: usize,
+}
+
+impl<'a> Tokenizer<'a> {
+    fn new(input: &'a str) -> Self {
+        Tokenizer { input, position: 0 }
+    }
+}
+
+impl<'a> Iterator for Tokenizer<'a> {
+    type Item = Token<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bytes = self.input.as_bytes();
+
+        while let Some(found) = self.input[self.position..].find('(') {
+            let open = self.position + found;
+            self.position = open + 1;
+
+            // instruction name is the identifier right before the bracket
+            let mut start = open;
+            while start > 0 && is_name_byte(bytes[start - 1]) {
+                start -= 1;
+            }
+
+            let mut close = open + 1;
+            while close < bytes.len() && (bytes[close].is_ascii_digit() || bytes[close] == b',') {
+                close += 1;
+            }
+            if close == bytes.len() || bytes[close] != b')' {
+                continue;
+            }
+
+            let name = &self.input[start..open];
+            let args = &self.input[open + 1..close];
+            if let Some((offset, instruction)) = decode(name, args) {
+                self.position = close + 1;
+                return Some(Token {
+                    span: start + offset..close + 1,
+                    instruction,
+                });
+            }
+        }
+
+        self.position = self.input.len();
+        None
+    }
+}
+
+fn is_name_byte(byte: u8) -> bool {
+    byte.is_ascii_alphabetic() || byte == b'_' || byte == b'\''
+}
+
+// Known instructions may be glued to other characters (`xmul(2,4)`, `undo()`),
+// so they are matched as a suffix of the name. Returns offset of the
+// instruction within the name together with decoded instruction.
+fn decode<'a>(name: &'a str, args: &'a str) -> Option<(usize, Instruction<'a>)> {
+    if name.ends_with("mul") {
+        let (first, second) = args.split_once(',')?;
+        let valid = |n: &str| (1..=3).contains(&n.len()) && n.bytes().all(|b| b.is_ascii_digit());
+        if !valid(first) || !valid(second) {
+            return None;
+        }
+        let instruction = Instruction::Mul(first.parse().ok()?, second.parse().ok()?);
+        return Some((name.len() - "mul".len(), instruction));
+    }
+
+    if name.ends_with("don't") {
+        return args
+            .is_empty()
+            .then_some((name.len() - "don't".len(), Instruction::Dont));
+    }
+
+    if name.ends_with("do") {
+        return args
+            .is_empty()
+            .then_some((name.len() - "do".len(), Instruction::Do));
+    }
+
+    let well_formed = args.is_empty()
+        || args
+            .split(',')
+            .all(|a| !a.is_empty() && a.bytes().all(|b| b.is_ascii_digit()));
+    if name.is_empty() || !well_formed {
+        return None;
+    }
+
+    Some((0, Instruction::Unknown { name, args }))
+}
+
+#[derive(Debug)]
+struct Machine {
+    conditionals: bool,
+    enabled: bool,
+    sum: u32,
+}
+
+impl Machine {
+    fn new(conditionals: bool) -> Self {
+        Machine {
+            conditionals,
+            enabled: true,
+            sum: 0,
+        }
+    }
+
+    fn execute(&mut self, instruction: &Instruction) {
+        match instruction {
+            Instruction::Mul(first, second) => {
+                if self.enabled {
+                    self.sum += first * second;
+                }
+            }
+            Instruction::Do => self.enabled = true,
+            Instruction::Dont => self.enabled = !self.conditionals,
+            Instruction::Unknown { .. } => {}
+        }
+    }
+}
+
+fn run(input: &str, conditionals: bool) -> u32 {
+    let mut machine = Machine::new(conditionals);
+    Tokenizer::new(input).for_each(|token| machine.execute(&token.instruction));
+    machine.sum
+}
+
+const RESET: &str = "\x1b[0m";
+const DIM: &str = "\x1b[2m";
+const GREEN: &str = "\x1b[1;32m";
+const RED: &str = "\x1b[31m";
+const YELLOW: &str = "\x1b[1;33m";
+const CYAN: &str = "\x1b[36m";
+
+// Annotated memory followed by listing of all mul() calls with running total.
+// Without colors instructions are wrapped in brackets and prefixed with marker:
+// `+` counted mul, `-` disabled mul, `?` unknown instruction.
+pub fn render(input: &str, conditionals: bool, colored: bool) -> String {
+    let mut machine = Machine::new(conditionals);
+    let mut memory = String::new();
+    let mut listing = String::new();
+    let mut last = 0;
+
+    for token in Tokenizer::new(input) {
+        let gap = &input[last..token.span.start];
+        if colored && !machine.enabled {
+            write!(memory, "{}{}{}", DIM, gap, RESET).unwrap();
+        } else {
+            memory.push_str(gap);
+        }
+
+        let enabled = machine.enabled;
+        machine.execute(&token.instruction);
+
+        let text = &input[token.span.clone()];
+        let (marker, color) = match token.instruction {
+            Instruction::Mul(..) if enabled => ("+", GREEN),
+            Instruction::Mul(..) => ("-", RED),
+            Instruction::Do | Instruction::Dont => ("", YELLOW),
+            Instruction::Unknown { .. } => ("?", CYAN),
+        };
+        if colored {
+            write!(memory, "{}{}{}", color, text, RESET).unwrap();
+        } else {
+            write!(memory, "[{}{}]", marker, text).unwrap();
+        }
+
+        if let Instruction::Mul(first, second) = token.instruction {
+            writeln!(
+                listing,
+                "{:>8} {:<14} {:<8} {:>8} {:>10}",
+                token.span.start,
+                text,
+                if enabled { "counted" } else { "disabled" },
+                first * second,
+                machine.sum
+            )
+            .unwrap();
+        }
+
+        last = token.span.end;
+    }
+
+    let rest = &input[last..];
+    if colored && !machine.enabled {
+        write!(memory, "{}{}{}", DIM, rest, RESET).unwrap();
+    } else {
+        memory.push_str(rest);
+    }
+
+    format!(
+        "{}\n\n{:>8} {:<14} {:<8} {:>8} {:>10}\n{}",
+        memory, "offset", "instruction", "status", "product", "total", listing
+    )
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Keyword {
+    Mul,
+    Do,
+    Dont,
+}
+
+impl Keyword {
+    fn bytes(&self) -> &'static [u8] {
+        match self {
+            Keyword::Mul => b"mul(",
+            Keyword::Do => b"do()",
+            Keyword::Dont => b"don't()",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum State {
+    Idle,
+    // keyword with amount of already matched bytes
+    Keyword(Keyword, usize),
+    // value and digit count of the first argument
+    First(u32, usize),
+    // first argument, value and digit count of the second one
+    Second(u32, u32, usize),
+}
+
+// Single pass scanner over raw bytes, it doesn't allocate and yields
+// the same pairs as `parse_data` (or only enabled ones with conditionals).
+struct Scanner<'a> {
+    bytes: &'a [u8],
+    position: usize,
+    state: State,
+    conditionals: bool,
+    enabled: bool,
+}
+
+impl<'a> Scanner<'a> {
+    fn new(input: &'a str, conditionals: bool) -> Self {
+        Scanner {
+            bytes: input.as_bytes(),
+            position: 0,
+            state: State::Idle,
+            conditionals,
+            enabled: true,
+        }
+    }
+
+    fn step(&mut self, byte: u8) -> Option<(u32, u32)> {
+        let digit = |byte: u8| (byte - b'0') as u32;
+
+        let (state, pair) = match (self.state, byte) {
+            (State::Keyword(keyword, matched), _) if keyword.bytes()[matched] == byte => {
+                if matched + 1 < keyword.bytes().len() {
+                    (State::Keyword(keyword, matched + 1), None)
+                } else {
+                    match keyword {
+                        Keyword::Mul => (State::First(0, 0), None),
+                        Keyword::Do => {
+                            self.enabled = true;
+                            (State::Idle, None)
+                        }
+                        Keyword::Dont => {
+                            self.enabled = !self.conditionals;
+                            (State::Idle, None)
+                        }
+                    }
+                }
+            }
+            (State::Keyword(Keyword::Do, 2), b'n') => (State::Keyword(Keyword::Dont, 3), None),
+            (State::First(value, digits), b'0'..=b'9') if digits < 3 => {
+                (State::First(value * 10 + digit(byte), digits + 1), None)
+            }
+            (State::First(value, digits), b',') if digits > 0 => (State::Second(value, 0, 0), None),
+            (State::Second(first, value, digits), b'0'..=b'9') if digits < 3 => (
+                State::Second(first, value * 10 + digit(byte), digits + 1),
+                None,
+            ),
+            (State::Second(first, value, digits), b')') if digits > 0 => {
+                (State::Idle, Some((first, value)))
+            }
+            // no keyword contains `m` or `d` past its first byte,
+            // so a mismatch only needs to restart on the current byte
+            (_, b'm') => (State::Keyword(Keyword::Mul, 1), None),
+            (_, b'd') => (State::Keyword(Keyword::Do, 1), None),
+            _ => (State::Idle, None),
+        };
+
+        self.state = state;
+        pair.filter(|_| self.enabled)
+    }
+}
+
+impl Iterator for Scanner<'_> {
+    type Item = (u32, u32);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.position < self.bytes.len() {
+            let byte = self.bytes[self.position];
+            self.position += 1;
+            if let Some(pair) = self.step(byte) {
+                return Some(pair);
+            }
+        }
+        None
+    }
+}
+
+pub fn scan(input: &str, conditionals: bool) -> u32 {
+    Scanner::new(input, conditionals)
+        .map(|(first, second)| first * second)
+        .sum()
+}
+
+pub fn benchmark(input: &str, iterations: u32) {
+    let measure = |name: &str, f: &dyn Fn() -> u32| {
+        let start = Instant::now();
+        let mut result = 0;
+        for _ in 0..iterations {
+            result = f();
+        }
+        println!(
+            "{:<24} {:>10} {:>12.2?}/iter",
+            name,
+            result,
+            start.elapsed() / iterations
+        );
+    };
+
+    measure("regex part 1", &|| multiply(&parse_data(input)));
+    measure("interpreter part 1", &|| run(input, false));
+    measure("interpreter part 2", &|| run(input, true));
+    measure("scanner part 1", &|| scan(input, false));
+    measure("scanner part 2", &|| scan(input, true));
+}
+
+pub struct Day03;
+
+impl Solver for Day03 {
+    fn day(&self) -> u32 {
+        3
+    }
+
+    fn part1(&self, input: &str) -> Answer {
+        Ok(scan(input, false).to_string())
+    }
+
+    fn part2(&self, input: &str) -> Answer {
+        Ok(scan(input, true).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{multiply, parse_data, render, run, scan, Instruction, Scanner, Tokenizer};
+
+    #[test]
+    fn test_parse_data() {
+        let input = "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
+
+        let pairs = parse_data(input);
+        assert_eq!(pairs.len(), 4);
+    }
+
+    #[test]
+    fn test_day3_part1() {
+        let input = "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
+
+        let pairs = parse_data(input);
+        assert_eq!(multiply(&pairs), 161);
+    }
+    #[test]
+    fn test_day3_part2() {
+        let input = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+        assert_eq!(run(input, true), 48);
+        assert_eq!(run(input, false), 161);
+    }
+
+    #[test]
+    fn test_tokenizer() {
+        let input = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+
+        let tokens = Tokenizer::new(input).collect::<Vec<_>>();
+        let instructions = tokens.iter().map(|t| t.instruction).collect::<Vec<_>>();
+        assert_eq!(
+            instructions,
+            vec![
+                Instruction::Mul(2, 4),
+                Instruction::Dont,
+                Instruction::Mul(5, 5),
+                Instruction::Mul(11, 8),
+                Instruction::Do,
+                Instruction::Mul(8, 5),
+            ]
+        );
+        assert_eq!(tokens[0].span, 1..9);
+        assert_eq!(&input[tokens[1].span.clone()], "don't()");
+        assert_eq!(&input[tokens[4].span.clone()], "do()");
+    }
+
+    #[test]
+    fn test_tokenizer_unknown() {
+        let input = "from()why(12,3)do_not_mul(5,5)what(1,x)select()mul(1234,5)";
+
+        let instructions = Tokenizer::new(input)
+            .map(|t| t.instruction)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            instructions,
+            vec![
+                Instruction::Unknown {
+                    name: "from",
+                    args: ""
+                },
+                Instruction::Unknown {
+                    name: "why",
+                    args: "12,3"
+                },
+                Instruction::Mul(5, 5),
+                Instruction::Unknown {
+                    name: "select",
+                    args: ""
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_scanner() {
+        let input = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+
+        assert_eq!(
+            Scanner::new(input, false).collect::<Vec<_>>(),
+            parse_data(input)
+        );
+        assert_eq!(
+            Scanner::new(input, true).collect::<Vec<_>>(),
+            vec![(2, 4), (8, 5)]
+        );
+        assert_eq!(scan(input, false), 161);
+        assert_eq!(scan(input, true), 48);
+    }
+
+    #[test]
+    fn test_scanner_equivalence() {
+        let cases = [
+            "mul(1,mul(2,3))",
+            "mmul(4,5)mul(1234,5)mul(12,3456)mul(,4)mul(4,)",
+            "dodon't()mul(1,1)ddo()mul(2,2)don'tmul(3,3)do(mul(4,4)",
+            "mul(999,999)mul(0,7)mul(007,1)",
+            "mul(1,2",
+        ];
+        for input in cases {
+            assert_eq!(
+                Scanner::new(input, false).collect::<Vec<_>>(),
+                parse_data(input),
+                "{}",
+                input
+            );
+            assert_eq!(scan(input, true), run(input, true), "{}", input);
+        }
+
+        // pseudo random memory made of characters that matter to both parsers
+        let alphabet = b"mul(,)don't123";
+        let mut seed: u64 = 0x2024_0003;
+        for _ in 0..200 {
+            let input = (0..200)
+                .map(|_| {
+                    seed ^= seed << 13;
+                    seed ^= seed >> 7;
+                    seed ^= seed << 17;
+                    alphabet[(seed % alphabet.len() as u64) as usize] as char
+                })
+                .collect::<String>();
+            assert_eq!(
+                Scanner::new(&input, false).collect::<Vec<_>>(),
+                parse_data(&input),
+                "{}",
+                input
+            );
+            assert_eq!(scan(&input, true), run(&input, true), "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_render() {
+        let input = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+
+        let output = render(input, true, false);
+        let mut lines = output.lines();
+        assert_eq!(
+            lines.next(),
+            Some(
+                "x[+mul(2,4)]&mul[3,7]!^[don't()]_[-mul(5,5)]+mul(32,64]([-mul(11,8)]un[do()]?[+mul(8,5)])"
+            )
+        );
+        assert_eq!(lines.next(), Some(""));
+        lines.next();
+        assert_eq!(
+            lines.collect::<Vec<_>>(),
+            vec![
+                "       1 mul(2,4)       counted         8          8",
+                "      28 mul(5,5)       disabled       25          8",
+                "      48 mul(11,8)      disabled       88          8",
+                "      64 mul(8,5)       counted        40         48",
+            ]
+        );
+
+        let colored = render(input, true, true);
+        assert!(colored.contains("\x1b[1;32mmul(2,4)\x1b[0m"));
+        assert!(colored.contains("\x1b[2m_\x1b[0m"));
+    }
+}
@@ -0,0 +1,610 @@
+use std::{collections::HashSet, error::Error, fmt, time::Instant};
+
+use crate::{
+    parse::{self, ParseError},
+    solver::{Answer, Solver},
+};
+
+pub type Position = (usize, usize);
+
+#[derive(Debug, PartialEq)]
+pub enum GridError {
+    // empty or ragged
+    Parse(ParseError),
+    NonAscii { row: usize },
+}
+
+impl fmt::Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GridError::Parse(e) => write!(f, "{}", e),
+            GridError::NonAscii { row } => {
+                write!(f, "row {} contains non ASCII characters", row + 1)
+            }
+        }
+    }
+}
+
+impl Error for GridError {}
+
+impl From<ParseError> for GridError {
+    fn from(e: ParseError) -> Self {
+        GridError::Parse(e)
+    }
+}
+
+// Letters stored row by row, so any cell is a single index away.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Grid {
+    width: usize,
+    height: usize,
+    cells: Vec<u8>,
+}
+
+impl Grid {
+    fn get(&self, row: isize, col: isize) -> Option<u8> {
+        if row < 0 || col < 0 || row as usize >= self.height || col as usize >= self.width {
+            return None;
+        }
+        Some(self.cells[row as usize * self.width + col as usize])
+    }
+
+    fn rows(&self) -> impl Iterator<Item = &[u8]> {
+        self.cells.chunks(self.width)
+    }
+}
+
+pub fn parse_data(input: &str) -> Result<Grid, GridError> {
+    let rows = parse::char_grid(input)?;
+
+    let mut cells = Vec::with_capacity(rows[0].len() * rows.len());
+    for (row, line) in rows.iter().enumerate() {
+        if !line.iter().all(char::is_ascii) {
+            return Err(GridError::NonAscii { row });
+        }
+        cells.extend(line.iter().map(|&ch| ch as u8));
+    }
+
+    Ok(Grid {
+        width: rows[0].len(),
+        height: rows.len(),
+        cells,
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+    North,
+    NorthEast,
+}
+
+static DIRECTIONS: [Direction; 8] = [
+    Direction::East,
+    Direction::SouthEast,
+    Direction::South,
+    Direction::SouthWest,
+    Direction::West,
+    Direction::NorthWest,
+    Direction::North,
+    Direction::NorthEast,
+];
+
+impl Direction {
+    /// (row, col) step
+    pub fn delta(&self) -> (isize, isize) {
+        match self {
+            Direction::East => (0, 1),
+            Direction::SouthEast => (1, 1),
+            Direction::South => (1, 0),
+            Direction::SouthWest => (1, -1),
+            Direction::West => (0, -1),
+            Direction::NorthWest => (-1, -1),
+            Direction::North => (-1, 0),
+            Direction::NorthEast => (-1, 1),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Match<'a> {
+    pub word: &'a str,
+    /// (row, col) of the first letter
+    pub start: Position,
+    pub direction: Direction,
+}
+
+impl Match<'_> {
+    /// (row, col) of every letter, from the first one on.
+    pub fn positions(&self) -> impl Iterator<Item = Position> + '_ {
+        let (row, col) = self.start;
+        let (d_row, d_col) = self.direction.delta();
+        (0..self.word.len() as isize).map(move |i| {
+            (
+                (row as isize + i * d_row) as usize,
+                (col as isize + i * d_col) as usize,
+            )
+        })
+    }
+}
+
+// Searches every word in all eight directions, a single letter is found
+// once per cell instead of once per direction.
+pub fn find_words<'a>(grid: &Grid, words: &[&'a str]) -> Vec<Match<'a>> {
+    let mut matches = vec![];
+
+    for row in 0..grid.height {
+        for col in 0..grid.width {
+            for word in words.iter().filter(|w| !w.is_empty()) {
+                let directions = if word.len() == 1 {
+                    &DIRECTIONS[..1]
+                } else {
+                    &DIRECTIONS[..]
+                };
+                for &direction in directions {
+                    let (d_row, d_col) = direction.delta();
+                    let found = word.bytes().enumerate().all(|(i, b)| {
+                        let i = i as isize;
+                        grid.get(row as isize + i * d_row, col as isize + i * d_col) == Some(b)
+                    });
+                    if found {
+                        matches.push(Match {
+                            word,
+                            start: (row, col),
+                            direction,
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    matches
+}
+
+// Renders the grid the way puzzle text does, letters outside of matches are `.`.
+pub fn highlight(grid: &Grid, matches: &[Match]) -> String {
+    let used = matches
+        .iter()
+        .flat_map(|m| m.positions())
+        .collect::<HashSet<_>>();
+
+    grid.rows()
+        .enumerate()
+        .map(|(row, line)| {
+            line.iter()
+                .enumerate()
+                .map(|(col, &b)| {
+                    if used.contains(&(row, col)) {
+                        b as char
+                    } else {
+                        '.'
+                    }
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub fn get_match_count(grid: &Grid) -> u32 {
+    find_words(grid, &["XMAS"]).len() as u32
+}
+
+const X_MAS: &str = r"M.S
+    .A.
+    M.S";
+
+/// 2D stencil, `.` stands for any character.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pattern {
+    cells: Vec<Vec<Option<u8>>>,
+}
+
+impl Pattern {
+    /// One stencil row per line, leading whitespace is ignored and short rows
+    /// are padded with `.`.
+    pub fn parse(stencil: &str) -> Self {
+        let lines = stencil.lines().map(|l| l.trim()).collect::<Vec<_>>();
+        let width = lines.iter().map(|l| l.len()).max().unwrap_or(0);
+        let cells = lines
+            .iter()
+            .map(|line| {
+                let mut row = line
+                    .bytes()
+                    .map(|b| if b == b'.' { None } else { Some(b) })
+                    .collect::<Vec<_>>();
+                row.resize(width, None);
+                row
+            })
+            .collect();
+
+        Pattern { cells }
+    }
+
+    pub fn height(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn width(&self) -> usize {
+        self.cells.first().map_or(0, |r| r.len())
+    }
+
+    // 90 degrees clockwise
+    fn rotate(&self) -> Self {
+        let height = self.height();
+        let cells = (0..self.width())
+            .map(|row| {
+                (0..height)
+                    .map(|col| self.cells[height - 1 - col][row])
+                    .collect()
+            })
+            .collect();
+
+        Pattern { cells }
+    }
+
+    // mirror image along vertical axis
+    fn mirror(&self) -> Self {
+        let cells = self
+            .cells
+            .iter()
+            .map(|row| row.iter().rev().copied().collect())
+            .collect();
+
+        Pattern { cells }
+    }
+
+    /// All distinct rotations and mirror images, starting with the pattern itself.
+    pub fn variants(&self) -> Vec<Pattern> {
+        let mut variants: Vec<Pattern> = vec![];
+        for base in [self.clone(), self.mirror()] {
+            let mut current = base;
+            for _ in 0..4 {
+                let next = current.rotate();
+                if !variants.contains(&current) {
+                    variants.push(current);
+                }
+                current = next;
+            }
+        }
+        variants
+    }
+
+    fn matches_at(&self, grid: &Grid, row: usize, col: usize) -> bool {
+        self.cells.iter().enumerate().all(|(r, cells)| {
+            cells.iter().enumerate().all(|(c, cell)| {
+                let ch = grid.get((row + r) as isize, (col + c) as isize);
+                match cell {
+                    Some(expected) => ch == Some(*expected),
+                    None => ch.is_some(),
+                }
+            })
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Placement {
+    /// (row, col) of the top left corner
+    pub start: Position,
+    /// matched variant of the pattern
+    pub pattern: Pattern,
+}
+
+/// Every place the stencil fits, also rotated and mirrored with `transformations`.
+pub fn find_pattern(grid: &Grid, pattern: &Pattern, transformations: bool) -> Vec<Placement> {
+    let variants = if transformations {
+        pattern.variants()
+    } else {
+        vec![pattern.clone()]
+    };
+
+    let mut placements = vec![];
+    for row in 0..grid.height {
+        for col in 0..grid.width {
+            for variant in variants.iter() {
+                if variant.matches_at(grid, row, col) {
+                    placements.push(Placement {
+                        start: (row, col),
+                        pattern: variant.clone(),
+                    });
+                }
+            }
+        }
+    }
+
+    placements
+}
+
+pub fn get_match_count2(grid: &Grid) -> u32 {
+    find_pattern(grid, &Pattern::parse(X_MAS), true).len() as u32
+}
+
+// Original part 2 solution looking up cells with `chars().nth()`,
+// kept as a baseline for the benchmark.
+fn get_match_count2_chars(lines: &[&str]) -> u32 {
+    let mut count = 0;
+    let match_set = HashSet::from(['M', 'S']);
+
+    for row in 1..lines.len().saturating_sub(1) {
+        for col in 1..lines[0].len().saturating_sub(1) {
+            let ch = lines[row].chars().nth(col);
+            if ch == Some('A') {
+                let diagonal1 = HashSet::from([
+                    lines[row - 1].chars().nth(col - 1).unwrap(),
+                    lines[row + 1].chars().nth(col + 1).unwrap(),
+                ]);
+                let diagonal2 = HashSet::from([
+                    lines[row - 1].chars().nth(col + 1).unwrap(),
+                    lines[row + 1].chars().nth(col - 1).unwrap(),
+                ]);
+                if diagonal1 == match_set && diagonal2 == match_set {
+                    count += 1
+                }
+            }
+        }
+    }
+    count
+}
+
+// Repeats the grid horizontally to show how both approaches scale with line width.
+pub fn benchmark(grid: &Grid, iterations: u32) {
+    for repeat in [1, 4, 16] {
+        let lines = grid
+            .rows()
+            .map(|row| String::from_utf8(row.repeat(repeat)).unwrap())
+            .collect::<Vec<_>>();
+        let lines = lines.iter().map(|l| l.as_str()).collect::<Vec<_>>();
+        let wide = parse_data(&lines.join("\n")).unwrap();
+
+        let start = Instant::now();
+        let mut chars_count = 0;
+        for _ in 0..iterations {
+            chars_count = get_match_count2_chars(&lines);
+        }
+        let chars_time = start.elapsed() / iterations;
+
+        let start = Instant::now();
+        let mut grid_count = 0;
+        for _ in 0..iterations {
+            grid_count = get_match_count2(&wide);
+        }
+        let grid_time = start.elapsed() / iterations;
+
+        println!(
+            "{:>5}x{:<5} chars().nth: {} in {:.2?}, grid: {} in {:.2?}",
+            wide.width, wide.height, chars_count, chars_time, grid_count, grid_time
+        );
+    }
+}
+
+pub struct Day04;
+
+impl Solver for Day04 {
+    fn day(&self) -> u32 {
+        4
+    }
+
+    fn part1(&self, input: &str) -> Answer {
+        let grid = parse_data(input)?;
+        Ok(get_match_count(&grid).to_string())
+    }
+
+    fn part2(&self, input: &str) -> Answer {
+        let grid = parse_data(input)?;
+        Ok(get_match_count2(&grid).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        find_pattern, find_words, get_match_count, get_match_count2, get_match_count2_chars,
+        highlight, parse_data, Direction, GridError, Match, Pattern, X_MAS,
+    };
+    use crate::parse::ParseError;
+
+    #[test]
+    fn test_parse_data() {
+        let input = r"MMMSXXMASM
+            MSAMXMSMSA
+            AMXSXMAAMM
+            MSAMASMSMX
+            XMASAMXAMM
+            XXAMMXXAMA
+            SMSMSASXSS
+            SAXAMASAAA
+            MAMMMXMMMM
+            MXMXAXMASX";
+
+        let grid = parse_data(input).unwrap();
+        assert_eq!(grid.height, 10);
+        assert_eq!(grid.width, 10);
+        assert_eq!(grid.get(0, 4), Some(b'X'));
+        assert_eq!(grid.get(10, 0), None);
+        assert_eq!(grid.get(0, -1), None);
+    }
+
+    #[test]
+    fn test_match_count() {
+        let input = r"MMMSXXMASM
+            MSAMXMSMSA
+            AMXSXMAAMM
+            MSAMASMSMX
+            XMASAMXAMM
+            XXAMMXXAMA
+            SMSMSASXSS
+            SAXAMASAAA
+            MAMMMXMMMM
+            MXMXAXMASX";
+
+        let grid = parse_data(input).unwrap();
+        let match_count = get_match_count(&grid);
+        assert_eq!(match_count, 18);
+    }
+
+    #[test]
+    fn test_match_count2() {
+        let input = r"MMMSXXMASM
+            MSAMXMSMSA
+            AMXSXMAAMM
+            MSAMASMSMX
+            XMASAMXAMM
+            XXAMMXXAMA
+            SMSMSASXSS
+            SAXAMASAAA
+            MAMMMXMMMM
+            MXMXAXMASX";
+
+        let grid = parse_data(input).unwrap();
+        let match_count = get_match_count2(&grid);
+        assert_eq!(match_count, 9);
+
+        let lines = input.lines().map(|l| l.trim()).collect::<Vec<_>>();
+        assert_eq!(get_match_count2_chars(&lines), 9);
+    }
+
+    #[test]
+    fn test_find_words() {
+        let input = r"..X...
+            .SAMX.
+            .A..A.
+            XMAS.S
+            .X....";
+
+        let grid = parse_data(input).unwrap();
+        let matches = find_words(&grid, &["XMAS"]);
+        assert_eq!(matches.len(), 4);
+        assert!(matches.contains(&Match {
+            word: "XMAS",
+            start: (0, 2),
+            direction: Direction::SouthEast
+        }));
+        assert!(matches.contains(&Match {
+            word: "XMAS",
+            start: (4, 1),
+            direction: Direction::North
+        }));
+        let lines = input.lines().map(|l| l.trim()).collect::<Vec<_>>();
+        assert_eq!(highlight(&grid, &matches), lines.join("\n"));
+    }
+
+    #[test]
+    fn test_find_multiple_words() {
+        let input = r"CAT
+            ODO
+            GOT";
+
+        let grid = parse_data(input).unwrap();
+        let matches = find_words(&grid, &["CAT", "COG", "TOT"]);
+        assert_eq!(
+            matches
+                .iter()
+                .map(|m| (m.word, m.start, m.direction))
+                .collect::<Vec<_>>(),
+            vec![
+                ("CAT", (0, 0), Direction::East),
+                ("COG", (0, 0), Direction::South),
+                ("TOT", (0, 2), Direction::South),
+                ("TOT", (2, 2), Direction::North),
+            ]
+        );
+        assert_eq!(highlight(&grid, &matches[..2]), "CAT\nO..\nG..");
+    }
+
+    #[test]
+    fn test_find_single_letter() {
+        let grid = parse_data("X.\n.X").unwrap();
+        let matches = find_words(&grid, &["X"]);
+        assert_eq!(
+            matches.iter().map(|m| m.start).collect::<Vec<_>>(),
+            vec![(0, 0), (1, 1)]
+        );
+    }
+
+    #[test]
+    fn test_pattern_transformations() {
+        let pattern = Pattern::parse("AB.");
+        assert_eq!(pattern.rotate(), Pattern::parse("A\nB\n."));
+        assert_eq!(pattern.mirror(), Pattern::parse(".BA"));
+        assert_eq!(pattern.variants().len(), 4);
+        assert_eq!(pattern.variants()[0], pattern);
+
+        let x_mas = Pattern::parse(X_MAS);
+        assert_eq!(x_mas.height(), 3);
+        assert_eq!(x_mas.width(), 3);
+        assert_eq!(x_mas.variants().len(), 4);
+    }
+
+    #[test]
+    fn test_find_pattern() {
+        let input = r"AB.AB
+            .BA..
+            BA.XB";
+
+        let grid = parse_data(input).unwrap();
+        let pattern = Pattern::parse("AB");
+
+        let placements = find_pattern(&grid, &pattern, false);
+        assert_eq!(
+            placements.iter().map(|p| p.start).collect::<Vec<_>>(),
+            vec![(0, 0), (0, 3)]
+        );
+
+        let placements = find_pattern(&grid, &pattern, true);
+        assert_eq!(
+            placements
+                .iter()
+                .map(|p| (p.start, p.pattern.clone()))
+                .collect::<Vec<_>>(),
+            vec![
+                ((0, 0), Pattern::parse("AB")),
+                ((0, 3), Pattern::parse("AB")),
+                ((1, 1), Pattern::parse("BA")),
+                ((1, 1), Pattern::parse("B\nA")),
+                ((2, 0), Pattern::parse("BA")),
+            ]
+        );
+
+        let wildcard = Pattern::parse("B.\n.A");
+        let placements = find_pattern(&grid, &wildcard, false);
+        assert_eq!(
+            placements.iter().map(|p| p.start).collect::<Vec<_>>(),
+            vec![(0, 1)]
+        );
+    }
+
+    #[test]
+    fn test_invalid_grid() {
+        assert!(matches!(parse_data(""), Err(GridError::Parse(_))));
+        assert!(matches!(parse_data("\n  \n"), Err(GridError::Parse(_))));
+        assert!(matches!(
+            parse_data("XMAS\nXMA\nXMAS"),
+            Err(GridError::Parse(ParseError { line: 2, .. }))
+        ));
+        assert_eq!(
+            parse_data("XMAS\nXMÄS"),
+            Err(GridError::NonAscii { row: 1 })
+        );
+        assert_eq!(
+            parse_data("XMAS\nXMA").unwrap_err().to_string(),
+            "line 2, column 1: row has 3 characters, expected 4"
+        );
+    }
+
+    #[test]
+    fn test_small_grid() {
+        let grid = parse_data("MAS").unwrap();
+        assert_eq!(get_match_count(&grid), 0);
+        assert_eq!(get_match_count2(&grid), 0);
+
+        let grid = parse_data("SAMX").unwrap();
+        assert_eq!(get_match_count(&grid), 1);
+    }
+}
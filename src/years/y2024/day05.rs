@@ -0,0 +1,204 @@
+use std::cmp::Ordering;
+
+use crate::{
+    parse::{self, ParseError},
+    solver::{Answer, Solver},
+};
+
+type Predicate = fn(&[u32], &[(u32, u32)]) -> bool;
+
+fn parse_rules(input: &str) -> Result<Vec<(u32, u32)>, ParseError> {
+    match parse::sections(input).first() {
+        Some(rules) => parse::lines(*rules, |line| parse::pair(line, "|")),
+        None => Ok(vec![]),
+    }
+}
+
+fn parse_pages(input: &str) -> Result<Vec<Vec<u32>>, ParseError> {
+    match parse::sections(input).get(1) {
+        Some(pages) => parse::lines(*pages, |line| parse::separated(line, ",")),
+        None => Ok(vec![]),
+    }
+}
+
+pub fn get_filtered_pages(
+    input: &str,
+    filter_predicate: Predicate,
+) -> Result<Vec<Vec<u32>>, ParseError> {
+    let rules = parse_rules(input)?;
+    let pages = parse_pages(input)?;
+
+    let ordered_pages = pages
+        .into_iter()
+        .filter(|p| filter_predicate(p, &rules))
+        .collect();
+
+    Ok(ordered_pages)
+}
+
+pub fn filter_ordered_predicate(pages: &[u32], rules: &[(u32, u32)]) -> bool {
+    pages.windows(2).all(|pair| {
+        let first = pair[0];
+        let second = pair[1];
+
+        let result = comparator(rules, first, second);
+        result != Ordering::Less
+    })
+}
+
+fn filter_unordered_predicate(pages: &[u32], rules: &[(u32, u32)]) -> bool {
+    pages.windows(2).any(|pair| {
+        let first = pair[0];
+        let second = pair[1];
+
+        let result = comparator(rules, first, second);
+        result == Ordering::Less
+    })
+}
+
+fn comparator(rules: &[(u32, u32)], a: u32, b: u32) -> Ordering {
+    if rules.contains(&(a, b)) {
+        return Ordering::Greater;
+    }
+
+    if rules.contains(&(b, a)) {
+        return Ordering::Less;
+    }
+
+    Ordering::Equal
+}
+
+pub fn get_sum_middle(ordered_pages: &[Vec<u32>]) -> u32 {
+    ordered_pages
+        .iter()
+        .map(|line| line.get(line.len() / 2).unwrap())
+        .sum()
+}
+
+pub fn fix_order(input: &str) -> Result<Vec<Vec<u32>>, ParseError> {
+    let mut unordered_pages = get_filtered_pages(input, filter_unordered_predicate)?;
+    let rules = parse_rules(input)?;
+
+    let predicate = |a, b| {
+        if rules.contains(&(a, b)) {
+            return Ordering::Greater;
+        }
+        if rules.contains(&(b, a)) {
+            return Ordering::Less;
+        }
+        Ordering::Equal
+    };
+
+    for pages in unordered_pages.iter_mut() {
+        pages.sort_by(|&a, &b| predicate(a, b));
+    }
+
+    Ok(unordered_pages)
+}
+
+pub struct Day05;
+
+impl Solver for Day05 {
+    fn day(&self) -> u32 {
+        5
+    }
+
+    fn part1(&self, input: &str) -> Answer {
+        let ordered_pages = get_filtered_pages(input, filter_ordered_predicate)?;
+        Ok(get_sum_middle(&ordered_pages).to_string())
+    }
+
+    fn part2(&self, input: &str) -> Answer {
+        let fixed_ordered_pages = fix_order(input)?;
+        Ok(get_sum_middle(&fixed_ordered_pages).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+
+    use super::{
+        filter_ordered_predicate, filter_unordered_predicate, get_filtered_pages, get_sum_middle,
+        parse_pages, parse_rules,
+    };
+
+    #[test]
+    fn test_parse_data() {
+        let input = r"47|53
+            97|13
+            97|61
+            97|47
+            75|29
+            61|13
+            75|53
+            29|13
+            97|29
+            53|29
+            61|53
+            97|53
+            61|29
+            47|13
+            75|47
+            97|75
+            47|61
+            75|61
+            47|29
+            75|13
+            53|13
+
+            75,47,61,53,29
+            97,61,53,29,13
+            75,29,13
+            75,97,47,61,53
+            61,13,29
+            97,13,75,29,47";
+
+        let rules = parse_rules(input).unwrap();
+        let pages = parse_pages(input).unwrap();
+        assert_eq!(rules.len(), 21);
+        assert_eq!(pages.len(), 6);
+
+        let ordered_pages = get_filtered_pages(input, filter_ordered_predicate).unwrap();
+        assert_eq!(ordered_pages.len(), 3);
+
+        let sum_middle = get_sum_middle(&ordered_pages);
+        assert_eq!(sum_middle, 143);
+
+        let mut unordered_pages = get_filtered_pages(input, filter_unordered_predicate).unwrap();
+        assert_eq!(unordered_pages.len(), 3);
+
+        let predicate = |a, b| {
+            if rules.contains(&(a, b)) {
+                return Ordering::Greater;
+            }
+            if rules.contains(&(b, a)) {
+                return Ordering::Less;
+            }
+            Ordering::Equal
+        };
+
+        let fixed_ordered_pages = unordered_pages
+            .iter_mut()
+            .map(|p| {
+                p.sort_by(|a, b| predicate(*a, *b));
+
+                p.clone()
+            })
+            .collect::<Vec<_>>();
+        let sum_middle = get_sum_middle(&fixed_ordered_pages);
+        assert_eq!(sum_middle, 123);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let input = "47|53\n97-13\n\n75,47,61";
+        let error = parse_rules(input).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.message, "expected '|'");
+
+        let input = "47|53\n\n75,47,61\n97,,13";
+        let error = parse_pages(input).unwrap_err();
+        assert_eq!((error.line, error.column), (4, 4));
+    }
+}
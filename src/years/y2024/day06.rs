@@ -0,0 +1,803 @@
+use std::{
+    collections::HashSet,
+    error::Error,
+    fmt,
+    io::{self, Write},
+    sync::mpsc,
+    thread,
+    time::Duration,
+};
+
+use crate::{
+    parallel::Mode,
+    parse::{self, ParseError},
+    solver::{Answer, Solver},
+};
+
+pub type Position = (usize, usize);
+/// A guard position with the heading it has there.
+pub type Step = (Position, Direction);
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Direction {
+    North,
+    East,
+    West,
+    South,
+}
+
+impl Direction {
+    pub fn symbol(&self) -> char {
+        match self {
+            Direction::North => '^',
+            Direction::East => '>',
+            Direction::South => 'v',
+            Direction::West => '<',
+        }
+    }
+
+    fn from_symbol(ch: char) -> Option<Direction> {
+        DIRECTIONS.into_iter().find(|d| d.symbol() == ch)
+    }
+
+    fn turn_right(&self) -> Direction {
+        match self {
+            Direction::North => Direction::East,
+            Direction::East => Direction::South,
+            Direction::South => Direction::West,
+            Direction::West => Direction::North,
+        }
+    }
+}
+
+static DIRECTIONS: [Direction; 4] = [
+    Direction::North,
+    Direction::East,
+    Direction::South,
+    Direction::West,
+];
+
+#[derive(Debug, PartialEq)]
+pub enum MapError {
+    // empty or ragged
+    Parse(ParseError),
+    UnknownTile { position: Position, tile: char },
+    NoGuard,
+    MultipleGuards { first: Position, second: Position },
+    GuardLoops,
+}
+
+impl fmt::Display for MapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MapError::Parse(e) => write!(f, "{}", e),
+            MapError::UnknownTile { position, tile } => {
+                write!(f, "unknown tile '{}' at {:?}", tile, position)
+            }
+            MapError::NoGuard => write!(f, "no guard (^, >, v, <) on the map"),
+            MapError::MultipleGuards { first, second } => {
+                write!(
+                    f,
+                    "multiple guards on the map, at {:?} and {:?}",
+                    first, second
+                )
+            }
+            MapError::GuardLoops => write!(f, "the guard patrols in a loop and never leaves"),
+        }
+    }
+}
+
+impl Error for MapError {}
+
+impl From<ParseError> for MapError {
+    fn from(e: ParseError) -> Self {
+        MapError::Parse(e)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Game {
+    collides: u32,
+    direction: Direction,
+    board: (usize, usize),
+    pub guard: Option<Position>,
+    crates: HashSet<(usize, usize)>,
+    pub visited: HashSet<(usize, usize)>,
+    // every position and heading so far, seeing one again means a loop
+    seen: HashSet<Step>,
+    looping: bool,
+    // ordered guard positions with heading, recorded only on demand
+    path: Option<Vec<Step>>,
+}
+
+impl Game {
+    pub fn from(input: &str) -> Result<Self, MapError> {
+        let rows = parse::char_grid(input)?;
+
+        let mut crates = HashSet::new();
+        let mut guard: Option<(Position, Direction)> = None;
+
+        for (j, row) in rows.iter().enumerate() {
+            for (i, &ch) in row.iter().enumerate() {
+                match (ch, Direction::from_symbol(ch)) {
+                    ('#', _) => {
+                        crates.insert((i, j));
+                    }
+                    ('.', _) => {}
+                    (_, Some(direction)) => {
+                        if let Some((first, _)) = guard {
+                            return Err(MapError::MultipleGuards {
+                                first,
+                                second: (i, j),
+                            });
+                        }
+                        guard = Some(((i, j), direction));
+                    }
+                    (tile, None) => {
+                        return Err(MapError::UnknownTile {
+                            position: (i, j),
+                            tile,
+                        })
+                    }
+                }
+            }
+        }
+
+        let (position, direction) = guard.ok_or(MapError::NoGuard)?;
+
+        Ok(Self {
+            collides: 0,
+            direction,
+            board: (rows[0].len(), rows.len()),
+            guard: Some(position),
+            crates,
+            visited: HashSet::from([position]),
+            seen: HashSet::from([(position, direction)]),
+            looping: false,
+            path: None,
+        })
+    }
+
+    // Starts recording every step (move or turn) from the current position.
+    pub fn record_path(&mut self) {
+        self.path = self.guard.map(|position| vec![(position, self.direction)]);
+    }
+
+    /// Recorded steps in order, empty unless [`Game::record_path`] was called.
+    pub fn path(&self) -> impl Iterator<Item = &Step> {
+        self.path.iter().flatten()
+    }
+
+    /// Whether stepping goes on: the guard is on the board and not in a loop.
+    pub fn is_patrolling(&self) -> bool {
+        self.guard.is_some() && !self.looping
+    }
+
+    pub fn is_looping(&self) -> bool {
+        self.looping
+    }
+
+    pub fn turns(&self) -> u32 {
+        self.collides
+    }
+
+    pub fn path_csv(&self) -> String {
+        let mut csv = String::from("step,x,y,direction\n");
+        for (i, ((x, y), direction)) in self.path().enumerate() {
+            csv.push_str(&format!("{},{},{},{:?}\n", i, x, y, direction));
+        }
+        csv
+    }
+
+    // Draws the recorded path like the puzzle text does: `|` and `-` for
+    // vertical and horizontal moves, `+` where the guard turned or crossed its path.
+    pub fn render_path(&self) -> String {
+        let (width, height) = self.board;
+        let mut tiles = vec![vec!['.'; width]; height];
+        for &(x, y) in self.crates.iter() {
+            tiles[y][x] = '#';
+        }
+
+        let mut previous: Option<&Step> = None;
+        for step in self.path() {
+            let ((x, y), direction) = *step;
+            let tile = if previous.is_some_and(|(p, _)| *p == (x, y)) {
+                '+'
+            } else {
+                match direction {
+                    Direction::North | Direction::South => '|',
+                    Direction::East | Direction::West => '-',
+                }
+            };
+            tiles[y][x] = match (tiles[y][x], tile) {
+                ('.', tile) => tile,
+                (current, tile) if current == tile => tile,
+                _ => '+',
+            };
+            previous = Some(step);
+        }
+
+        if let Some(((x, y), direction)) = self.path().next() {
+            tiles[*y][*x] = direction.symbol();
+        }
+
+        tiles
+            .iter()
+            .map(|row| row.iter().collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    pub fn step(&mut self) {
+        let next_position = self.get_next_position();
+
+        if let Some(position) = next_position {
+            if self.crates.contains(&position) {
+                self.collides += 1;
+                self.direction = self.direction.turn_right();
+                if let Some(path) = self.path.as_mut() {
+                    path.push((self.guard.unwrap(), self.direction));
+                }
+            } else {
+                self.guard = next_position;
+                self.visited.insert(position);
+                if let Some(path) = self.path.as_mut() {
+                    path.push((position, self.direction));
+                }
+            }
+        } else {
+            self.guard = None;
+        }
+
+        if let Some(position) = self.guard {
+            self.looping |= !self.seen.insert((position, self.direction));
+        }
+    }
+
+    fn get_current_direction(&self) -> Direction {
+        self.direction
+    }
+
+    fn get_next_position(&self) -> Option<Position> {
+        let current_direction = self.get_current_direction();
+        let (x, y) = self.guard.unwrap();
+        let (width, height) = self.board;
+        match current_direction {
+            Direction::North => {
+                if y == 0 {
+                    None
+                } else {
+                    Some((x, y - 1))
+                }
+            }
+            Direction::South => {
+                if y == height - 1 {
+                    None
+                } else {
+                    Some((x, y + 1))
+                }
+            }
+            Direction::East => {
+                if x == width - 1 {
+                    None
+                } else {
+                    Some((x + 1, y))
+                }
+            }
+            Direction::West => {
+                if x == 0 {
+                    None
+                } else {
+                    Some((x - 1, y))
+                }
+            }
+        }
+    }
+
+    fn render(&self) -> String {
+        let (width, height) = self.board;
+        let mut output = String::with_capacity((width + 1) * height);
+
+        for y in 0..height {
+            for x in 0..width {
+                let ch = if self.guard == Some((x, y)) {
+                    self.get_current_direction().symbol()
+                } else if self.crates.contains(&(x, y)) {
+                    '#'
+                } else if self.visited.contains(&(x, y)) {
+                    'X'
+                } else {
+                    '.'
+                };
+                output.push(ch);
+            }
+            output.push('\n');
+        }
+
+        output
+    }
+}
+
+// Obstacles sorted per row and per column, so the guard can jump straight
+// to the cell in front of the next obstacle instead of moving cell by cell.
+#[derive(Debug)]
+struct ObstacleIndex {
+    board: (usize, usize),
+    rows: Vec<Vec<usize>>,
+    columns: Vec<Vec<usize>>,
+}
+
+impl ObstacleIndex {
+    fn new(game: &Game) -> Self {
+        let (width, height) = game.board;
+        let mut rows = vec![vec![]; height];
+        let mut columns = vec![vec![]; width];
+
+        for &(x, y) in game.crates.iter() {
+            rows[y].push(x);
+            columns[x].push(y);
+        }
+        rows.iter_mut().for_each(|r| r.sort());
+        columns.iter_mut().for_each(|c| c.sort());
+
+        ObstacleIndex {
+            board: game.board,
+            rows,
+            columns,
+        }
+    }
+
+    // Cell in front of the next obstacle, `None` when the guard leaves the board.
+    // `extra` is a temporary obstacle that is not part of the index.
+    fn next_stop(
+        &self,
+        position: Position,
+        direction: Direction,
+        extra: Option<Position>,
+    ) -> Option<Position> {
+        let (x, y) = position;
+        let nearest_after = |a: Option<usize>, b: Option<usize>| match (a, b) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+
+        match direction {
+            Direction::North => {
+                let column = &self.columns[x];
+                let before = column[..column.partition_point(|&o| o < y)].last().copied();
+                let extra = extra
+                    .filter(|&(ex, ey)| ex == x && ey < y)
+                    .map(|(_, ey)| ey);
+                before.max(extra).map(|o| (x, o + 1))
+            }
+            Direction::South => {
+                let column = &self.columns[x];
+                let after = column[column.partition_point(|&o| o <= y)..]
+                    .first()
+                    .copied();
+                let extra = extra
+                    .filter(|&(ex, ey)| ex == x && ey > y)
+                    .map(|(_, ey)| ey);
+                nearest_after(after, extra).map(|o| (x, o - 1))
+            }
+            Direction::West => {
+                let row = &self.rows[y];
+                let before = row[..row.partition_point(|&o| o < x)].last().copied();
+                let extra = extra
+                    .filter(|&(ex, ey)| ey == y && ex < x)
+                    .map(|(ex, _)| ex);
+                before.max(extra).map(|o| (o + 1, y))
+            }
+            Direction::East => {
+                let row = &self.rows[y];
+                let after = row[row.partition_point(|&o| o <= x)..].first().copied();
+                let extra = extra
+                    .filter(|&(ex, ey)| ey == y && ex > x)
+                    .map(|(ex, _)| ex);
+                nearest_after(after, extra).map(|o| (o - 1, y))
+            }
+        }
+    }
+
+    fn edge(&self, position: Position, direction: Direction) -> Position {
+        let (x, y) = position;
+        let (width, height) = self.board;
+        match direction {
+            Direction::North => (x, 0),
+            Direction::South => (x, height - 1),
+            Direction::West => (0, y),
+            Direction::East => (width - 1, y),
+        }
+    }
+
+    // Follows the guard from stop to stop. Returns `None` when the patrol ends
+    // in a loop, otherwise all stops up to the last cell on the board.
+    fn patrol(
+        &self,
+        start: Position,
+        direction: Direction,
+        extra: Option<Position>,
+    ) -> Option<Vec<(Position, Direction)>> {
+        let mut seen = HashSet::new();
+        let mut stops = vec![(start, direction)];
+        let (mut position, mut direction) = (start, direction);
+
+        loop {
+            match self.next_stop(position, direction, extra) {
+                Some(stop) => {
+                    direction = direction.turn_right();
+                    if !seen.insert((stop, direction)) {
+                        return None;
+                    }
+                    position = stop;
+                    stops.push((stop, direction));
+                }
+                None => {
+                    stops.push((self.edge(position, direction), direction));
+                    return Some(stops);
+                }
+            }
+        }
+    }
+}
+
+fn cells_between(from: Position, to: Position) -> impl Iterator<Item = Position> {
+    let (x1, y1) = from;
+    let (x2, y2) = to;
+    (x1.min(x2)..=x1.max(x2)).flat_map(move |x| (y1.min(y2)..=y1.max(y2)).map(move |y| (x, y)))
+}
+
+// Same cells as stepping `Game` until the guard leaves, computed with jumps.
+fn visited_by_jumps(game: &Game) -> Result<HashSet<Position>, MapError> {
+    let index = ObstacleIndex::new(game);
+    let stops = index
+        .patrol(game.guard.unwrap(), game.get_current_direction(), None)
+        .ok_or(MapError::GuardLoops)?;
+
+    Ok(stops
+        .windows(2)
+        .flat_map(|pair| cells_between(pair[0].0, pair[1].0))
+        .collect())
+}
+
+// Amount of positions where a single extra obstacle traps the guard in a loop.
+pub fn count_loop_obstacles(game: &Game) -> Result<usize, MapError> {
+    loop_obstacles(game, Mode::default()).map(|obstacles| obstacles.len())
+}
+
+// Positions trapping the guard, every candidate is simulated on its own.
+fn loop_obstacles(game: &Game, mode: Mode) -> Result<Vec<Position>, MapError> {
+    let index = ObstacleIndex::new(game);
+    let start = game.guard.unwrap();
+    let direction = game.get_current_direction();
+
+    let mut candidates = visited_by_jumps(game)?
+        .into_iter()
+        .filter(|&candidate| candidate != start)
+        .collect::<Vec<_>>();
+    candidates.sort();
+
+    Ok(mode
+        .filter(&candidates, |&candidate| {
+            index.patrol(start, direction, Some(candidate)).is_none()
+        })
+        .into_iter()
+        .copied()
+        .collect())
+}
+
+fn frame(game: &Game, steps: usize) -> String {
+    format!(
+        "step {}, visited {}\n{}",
+        steps,
+        game.visited.len(),
+        game.render()
+    )
+}
+
+// Interactive animation, keys are read line by line from stdin:
+// `p` + Enter toggles pause, Enter (or `s`) steps once while paused, `q` quits.
+pub fn animate(game: &mut Game, delay: Duration) {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        for line in io::stdin().lines() {
+            let Ok(line) = line else { break };
+            if sender.send(line.trim().to_string()).is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut paused = false;
+    let mut steps = 0;
+    while game.is_patrolling() {
+        print!("\x1b[2J\x1b[H{}", frame(game, steps));
+        if paused {
+            println!("paused - Enter: step, p: resume, q: quit");
+        }
+        io::stdout().flush().unwrap();
+
+        let command = if paused {
+            receiver.recv().ok()
+        } else {
+            thread::sleep(delay);
+            receiver.try_recv().ok()
+        };
+        match command.as_deref() {
+            Some("q") => return,
+            Some("p") => {
+                paused = !paused;
+                continue;
+            }
+            _ => {}
+        }
+
+        game.step();
+        steps += 1;
+    }
+
+    print!("\x1b[2J\x1b[H{}", frame(game, steps));
+}
+
+// Headless variant of the animation for logs.
+pub fn print_every(game: &mut Game, every: usize) {
+    let mut steps = 0;
+    while game.is_patrolling() {
+        if steps % every == 0 {
+            println!("{}", frame(game, steps));
+        }
+        game.step();
+        steps += 1;
+    }
+    println!("{}", frame(game, steps));
+}
+
+pub struct Day06;
+
+impl Solver for Day06 {
+    fn day(&self) -> u32 {
+        6
+    }
+
+    fn part1(&self, input: &str) -> Answer {
+        let game = Game::from(input)?;
+        Ok(visited_by_jumps(&game)?.len().to_string())
+    }
+
+    fn part2(&self, input: &str) -> Answer {
+        let game = Game::from(input)?;
+        Ok(count_loop_obstacles(&game)?.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        count_loop_obstacles, frame, loop_obstacles, visited_by_jumps, Day06, Direction, Game,
+        MapError, ObstacleIndex,
+    };
+    use crate::parse::ParseError;
+    use crate::{parallel::Mode, solver::Solver};
+
+    #[test]
+    fn test_parse_data() {
+        let input = r"....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...";
+
+        let mut game = Game::from(input).unwrap();
+        assert_eq!(game.guard, Some((4, 6)));
+        assert_eq!(game.get_current_direction(), Direction::North);
+        assert_eq!(game.crates.len(), 8);
+        assert!(game.crates.contains(&(4, 0)));
+        assert!(game.crates.contains(&(1, 6)));
+        assert!(game.crates.contains(&(6, 9)));
+
+        while game.guard.is_some() {
+            game.step();
+        }
+
+        assert_eq!(game.visited.len(), 41);
+    }
+
+    #[test]
+    fn test_render() {
+        let input = r"....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...";
+
+        let mut game = Game::from(input).unwrap();
+        assert_eq!(game.render(), format!("{}\n", input));
+
+        for _ in 0..6 {
+            game.step();
+        }
+        let board = game.render();
+        let rows = board.lines().collect::<Vec<_>>();
+        assert_eq!(rows[1], "....>....#");
+        assert_eq!(rows[2], "....X.....");
+        assert_eq!(rows[6], ".#..X.....");
+        assert!(frame(&game, 6).starts_with("step 6, visited 6\n"));
+    }
+
+    #[test]
+    fn test_jumps() {
+        let input = r"....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...";
+
+        let mut game = Game::from(input).unwrap();
+        let index = ObstacleIndex::new(&game);
+        assert_eq!(
+            index.next_stop((4, 6), Direction::North, None),
+            Some((4, 1))
+        );
+        assert_eq!(index.next_stop((4, 1), Direction::East, None), Some((8, 1)));
+        assert_eq!(
+            index.next_stop((4, 6), Direction::North, Some((4, 3))),
+            Some((4, 4))
+        );
+        assert_eq!(index.next_stop((7, 7), Direction::South, None), None);
+        assert!(index.patrol((4, 6), Direction::North, None).is_some());
+        assert!(index
+            .patrol((4, 6), Direction::North, Some((3, 6)))
+            .is_none());
+
+        let jumped = visited_by_jumps(&game).unwrap();
+        while game.guard.is_some() {
+            game.step();
+        }
+        assert_eq!(jumped, game.visited);
+
+        assert_eq!(count_loop_obstacles(&Game::from(input).unwrap()), Ok(6));
+
+        let start = Game::from(input).unwrap();
+        let sequential = loop_obstacles(&start, Mode::Sequential).unwrap();
+        assert_eq!(sequential.len(), 6);
+        for mode in Mode::all() {
+            assert_eq!(loop_obstacles(&start, mode).unwrap(), sequential);
+        }
+    }
+
+    #[test]
+    fn test_guard_headings() {
+        let game = Game::from("..#\n.>.\n...").unwrap();
+        assert_eq!(game.guard, Some((1, 1)));
+        assert_eq!(game.get_current_direction(), Direction::East);
+
+        let mut game = Game::from("...\n.v.\n.#.").unwrap();
+        assert_eq!(game.get_current_direction(), Direction::South);
+        game.step();
+        assert_eq!(game.get_current_direction(), Direction::West);
+        assert_eq!(game.guard, Some((1, 1)));
+
+        let game = Game::from("<..").unwrap();
+        assert_eq!(game.board, (3, 1));
+        assert_eq!(game.get_current_direction(), Direction::West);
+    }
+
+    #[test]
+    fn test_invalid_map() {
+        assert!(matches!(Game::from(""), Err(MapError::Parse(_))));
+        assert_eq!(Game::from("...\n.#.").unwrap_err(), MapError::NoGuard);
+        assert_eq!(
+            Game::from("^..\n..v").unwrap_err(),
+            MapError::MultipleGuards {
+                first: (0, 0),
+                second: (2, 1)
+            }
+        );
+        assert!(matches!(
+            Game::from("^..\n.."),
+            Err(MapError::Parse(ParseError { line: 2, .. }))
+        ));
+        assert_eq!(
+            Game::from("^.O").unwrap_err(),
+            MapError::UnknownTile {
+                position: (2, 0),
+                tile: 'O'
+            }
+        );
+        assert_eq!(
+            Game::from("...").unwrap_err().to_string(),
+            "no guard (^, >, v, <) on the map"
+        );
+    }
+
+    #[test]
+    fn test_guard_loops() {
+        let input = ".#..\n...#\n#^..\n..#.\n";
+        assert!(Day06.part1(input).is_err());
+        assert_eq!(
+            Day06.part2(input).unwrap_err().to_string(),
+            "the guard patrols in a loop and never leaves"
+        );
+
+        let mut game = Game::from(input).unwrap();
+        while game.is_patrolling() {
+            game.step();
+        }
+        assert!(game.is_looping());
+        assert!(game.guard.is_some());
+    }
+
+    #[test]
+    fn test_path() {
+        let input = r".#..
+...#
+.^..";
+
+        let mut game = Game::from(input).unwrap();
+        game.record_path();
+        while game.guard.is_some() {
+            game.step();
+        }
+
+        assert_eq!(
+            game.path().copied().collect::<Vec<_>>(),
+            vec![
+                ((1, 2), Direction::North),
+                ((1, 1), Direction::North),
+                ((1, 1), Direction::East),
+                ((2, 1), Direction::East),
+                ((2, 1), Direction::South),
+                ((2, 2), Direction::South),
+            ]
+        );
+        assert_eq!(game.turns(), 2);
+        assert_eq!(game.render_path(), ".#..\n.++#\n.^|.");
+        assert_eq!(
+            game.path_csv().lines().take(3).collect::<Vec<_>>(),
+            vec!["step,x,y,direction", "0,1,2,North", "1,1,1,North"]
+        );
+    }
+
+    #[test]
+    fn test_path_crossing() {
+        let input = r"....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...";
+
+        let mut game = Game::from(input).unwrap();
+        assert_eq!(game.path().count(), 0);
+
+        game.record_path();
+        while game.guard.is_some() {
+            game.step();
+        }
+        let rows = game.render_path();
+        let rows = rows.lines().collect::<Vec<_>>();
+        assert_eq!(rows[1], "....+---+#");
+        assert_eq!(rows[4], "..+-+-+#|.");
+        assert_eq!(rows[6], ".#+-^-+-+.");
+        assert_eq!(game.turns(), 10);
+    }
+}
@@ -0,0 +1,123 @@
+use std::vec;
+
+use crate::{
+    parallel::Mode,
+    parse::{self, ParseError},
+    solver::{Answer, Solver},
+};
+
+pub fn solve(
+    input: &str,
+    operators: &[impl Fn(u64, u64) -> u64 + Sync],
+) -> Result<u64, ParseError> {
+    let equations = parse_data(input)?;
+    Ok(sum_solvable(&equations, operators, Mode::default()))
+}
+
+fn sum_solvable(
+    equations: &[(u64, Vec<u64>)],
+    operators: &[impl Fn(u64, u64) -> u64 + Sync],
+    mode: Mode,
+) -> u64 {
+    mode.filter(equations, |(target, remaining)| {
+        match remaining.split_first() {
+            Some((&first, rest)) => has_solution(operators, *target, first, rest),
+            None => false,
+        }
+    })
+    .into_iter()
+    .map(|(result, _)| result)
+    .sum()
+}
+
+pub fn get_operators() -> Vec<impl Fn(u64, u64) -> u64> {
+    vec![|a, b| a + b, |a, b| a * b]
+}
+
+pub fn get_operator2() -> Vec<impl Fn(u64, u64) -> u64> {
+    vec![|a, b| a + b, |a, b| a * b, |a, b| {
+        format!("{}{}", a, b).parse::<u64>().unwrap()
+    }]
+}
+
+fn has_solution(
+    operators: &[impl Fn(u64, u64) -> u64],
+    target: u64,
+    sum: u64,
+    remaining: &[u64],
+) -> bool {
+    if sum > target {
+        return false;
+    }
+    if remaining.is_empty() {
+        target == sum
+    } else {
+        operators
+            .iter()
+            .any(|o| has_solution(operators, target, o(sum, remaining[0]), &remaining[1..]))
+    }
+}
+
+fn parse_data(input: &str) -> Result<Vec<(u64, Vec<u64>)>, ParseError> {
+    parse::lines(input, |line| parse::key_values(line, ":"))
+}
+
+pub struct Day07;
+
+impl Solver for Day07 {
+    fn day(&self) -> u32 {
+        7
+    }
+
+    fn part1(&self, input: &str) -> Answer {
+        Ok(solve(input, &get_operators())?.to_string())
+    }
+
+    fn part2(&self, input: &str) -> Answer {
+        Ok(solve(input, &get_operator2())?.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_operator2, get_operators, parse_data, solve, sum_solvable};
+    use crate::parallel::Mode;
+
+    const INPUT: &str = r"190: 10 19
+3267: 81 40 27
+83: 17 5
+156: 15 6
+7290: 6 8 6 15
+161011: 16 10 13
+192: 17 8 14
+21037: 9 7 18 13
+292: 11 6 16 20";
+
+    #[test]
+    fn test_parse_data() {
+        let operators = get_operators();
+        let solution = solve(INPUT, &operators).unwrap();
+        assert_eq!(solution, 3749);
+    }
+
+    #[test]
+    fn test_modes() {
+        let equations = parse_data(INPUT).unwrap();
+        let sequential = sum_solvable(&equations, &get_operator2(), Mode::Sequential);
+        assert_eq!(sequential, 11387);
+
+        for mode in Mode::all() {
+            assert_eq!(sum_solvable(&equations, &get_operator2(), mode), sequential);
+        }
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = parse_data("190: 10 19\n3267 81 40 27").unwrap_err();
+        assert_eq!(error.line, 2);
+        assert_eq!(error.message, "expected ':'");
+
+        let error = parse_data("190: 10 19\n3267: 81 4O 27").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 10));
+    }
+}
@@ -0,0 +1,634 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    parallel::Mode,
+    parse::{self, ParseError},
+    solver::{Answer, Solver},
+};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Point {
+    x: i32,
+    y: i32,
+}
+
+impl Point {
+    fn new(x: i32, y: i32) -> Self {
+        Point { x, y }
+    }
+
+    fn is_on_grid(&self, board: (u32, u32)) -> bool {
+        let (width, height) = board;
+        self.x >= 0 && self.y >= 0 && self.x < width as i32 && self.y < height as i32
+    }
+
+    fn add(&self, other: &Point) -> Point {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+
+    fn sub(&self, other: &Point) -> Point {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+
+    fn scale(&self, factor: i32) -> Point {
+        Point::new(self.x * factor, self.y * factor)
+    }
+}
+
+fn gcd(a: i32, b: i32) -> i32 {
+    if b == 0 {
+        a.abs()
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+// Describes where antinodes of a pair of antennas are. For antennas `a` and `b`
+// the k-th harmonic is at `a + k * step`, where `step` is `a - b` divided by
+// `ratio - 1`, so the first harmonic is `ratio` times farther from `b` than from `a`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AntinodeRule {
+    pub ratio: i32,
+    pub min_harmonic: i32,
+    /// Unbounded when `None`, antinodes stop at the edge of the map.
+    pub max_harmonic: Option<i32>,
+    /// Every antenna sharing its frequency with another one is an antinode.
+    pub antennas: bool,
+    /// Step by `a - b` divided by gcd(dx, dy).
+    pub reduce: bool,
+    /// Walk from `a` in both directions along the line through `a` and `b`,
+    /// each pair is then visited only once instead of once per antenna.
+    /// Harmonics are counted from `a` on both sides, so this equals ordered
+    /// pairs only for unbounded harmonics.
+    pub line: bool,
+}
+
+impl AntinodeRule {
+    pub fn part1() -> Self {
+        AntinodeRule {
+            ratio: 2,
+            min_harmonic: 1,
+            max_harmonic: Some(1),
+            antennas: false,
+            reduce: false,
+            line: false,
+        }
+    }
+
+    pub fn part2() -> Self {
+        AntinodeRule {
+            ratio: 2,
+            min_harmonic: 1,
+            max_harmonic: None,
+            antennas: true,
+            reduce: false,
+            line: false,
+        }
+    }
+
+    // every grid point exactly in line with two antennas
+    pub fn resonant() -> Self {
+        AntinodeRule {
+            reduce: true,
+            line: true,
+            ..AntinodeRule::part2()
+        }
+    }
+
+    /// Rejects harmonic ranges that can't contain any harmonic.
+    pub fn check(&self) -> Result<(), String> {
+        match self.max_harmonic {
+            Some(max) if max < self.min_harmonic => Err(format!(
+                "max harmonic {} is below min harmonic {}",
+                max, self.min_harmonic
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    fn step(&self, a: &Point, b: &Point) -> Option<Point> {
+        let diff = a.sub(b);
+        let divisor = self.ratio - 1;
+        if divisor <= 0 || diff.x % divisor != 0 || diff.y % divisor != 0 {
+            return None;
+        }
+
+        let step = Point::new(diff.x / divisor, diff.y / divisor);
+        if self.reduce {
+            let divisor = gcd(step.x, step.y);
+            return Some(Point::new(step.x / divisor, step.y / divisor));
+        }
+        Some(step)
+    }
+}
+
+pub fn solve_with(input: &str, rule: &AntinodeRule) -> Result<HashSet<Point>, ParseError> {
+    solve_filtered(input, rule, None)
+}
+
+// Antinodes produced only by antennas of given frequency, all when `None`.
+pub fn solve_filtered(
+    input: &str,
+    rule: &AntinodeRule,
+    frequency: Option<char>,
+) -> Result<HashSet<Point>, ParseError> {
+    let (data, board) = parse_data(input)?;
+
+    let selected = data
+        .iter()
+        .filter(|(ch, _)| frequency.is_none_or(|f| f == **ch))
+        .map(|(_, nodes)| nodes.as_slice())
+        .collect::<Vec<_>>();
+
+    Ok(collect_antinodes(&selected, rule, board, Mode::default()))
+}
+
+// Frequencies are independent, so each one is generated on its own.
+fn collect_antinodes(
+    frequencies: &[&[Point]],
+    rule: &AntinodeRule,
+    board: (u32, u32),
+    mode: Mode,
+) -> HashSet<Point> {
+    mode.map(frequencies, |nodes| frequency_antinodes(nodes, rule, board))
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+fn frequency_antinodes(nodes: &[Point], rule: &AntinodeRule, board: (u32, u32)) -> Vec<Point> {
+    let mut antinodes = vec![];
+    if rule.antennas && nodes.len() > 1 {
+        antinodes.extend(nodes.iter().filter(|n| n.is_on_grid(board)));
+    }
+
+    for (i, a) in nodes.iter().enumerate() {
+        for (j, b) in nodes.iter().enumerate() {
+            if i == j || (rule.line && j < i) {
+                continue;
+            }
+            let Some(step) = rule.step(a, b) else {
+                continue;
+            };
+
+            let directions = if rule.line {
+                vec![step, step.scale(-1)]
+            } else {
+                vec![step]
+            };
+            for step in directions {
+                let mut harmonic = rule.min_harmonic;
+                while rule.max_harmonic.is_none_or(|max| harmonic <= max) {
+                    let antinode = a.add(&step.scale(harmonic));
+                    if antinode.is_on_grid(board) {
+                        antinodes.push(antinode);
+                    } else if harmonic > 0 {
+                        // past `a` the walk only moves away from the grid
+                        break;
+                    }
+                    harmonic += 1;
+                }
+            }
+        }
+    }
+
+    antinodes
+}
+
+pub fn solve(input: &str) -> Result<HashSet<Point>, ParseError> {
+    solve_with(input, &AntinodeRule::part1())
+}
+
+pub fn solve2(input: &str) -> Result<HashSet<Point>, ParseError> {
+    solve_with(input, &AntinodeRule::part2())
+}
+
+// Original map with `#` on antinodes, antennas stay visible where they overlap.
+// With a frequency filter other antennas are hidden.
+pub fn render(input: &str, antinodes: &HashSet<Point>, frequency: Option<char>) -> String {
+    input
+        .lines()
+        .enumerate()
+        .map(|(j, line)| {
+            line.chars()
+                .enumerate()
+                .map(|(i, ch)| {
+                    let antenna = ch.is_alphanumeric() && frequency.is_none_or(|f| f == ch);
+                    if antenna {
+                        ch
+                    } else if antinodes.contains(&Point::new(i as i32, j as i32)) {
+                        '#'
+                    } else {
+                        '.'
+                    }
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// Rendered and expected maps side by side, differing cells marked with `^` below.
+pub fn diff_maps(actual: &str, expected: &str) -> String {
+    let actual_rows = actual.lines().map(|l| l.trim()).collect::<Vec<_>>();
+    let expected_rows = expected.lines().map(|l| l.trim()).collect::<Vec<_>>();
+    let width = actual_rows
+        .iter()
+        .chain(expected_rows.iter())
+        .map(|r| r.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    let mut output = format!("{:<w$}  {:<w$}\n", "actual", "expected", w = width);
+    let mut differences = 0;
+    for row in 0..actual_rows.len().max(expected_rows.len()) {
+        let left = actual_rows.get(row).copied().unwrap_or("");
+        let right = expected_rows.get(row).copied().unwrap_or("");
+        let markers = (0..width)
+            .map(|i| {
+                if left.chars().nth(i) != right.chars().nth(i) {
+                    differences += 1;
+                    '^'
+                } else {
+                    ' '
+                }
+            })
+            .collect::<String>();
+
+        output.push_str(&format!("{:<w$}  {:<w$}\n", left, right, w = width));
+        if !markers.trim().is_empty() {
+            output.push_str(&format!("{}  {}\n", markers, markers));
+        }
+    }
+    output.push_str(&format!("{} cells differ", differences));
+
+    output
+}
+
+type Antennas = HashMap<char, Vec<Point>>;
+
+// Antenna positions per frequency and the size of the map.
+fn parse_data(input: &str) -> Result<(Antennas, (u32, u32)), ParseError> {
+    let grid = parse::char_grid(input)?;
+    let mut positions = Antennas::new();
+
+    for (j, row) in grid.iter().enumerate() {
+        for (i, &ch) in row.iter().enumerate() {
+            if ch.is_alphanumeric() {
+                positions
+                    .entry(ch)
+                    .or_default()
+                    .push(Point::new(i as i32, j as i32));
+            }
+        }
+    }
+
+    Ok((positions, (grid[0].len() as u32, grid.len() as u32)))
+}
+
+pub struct Day08;
+
+impl Solver for Day08 {
+    fn day(&self) -> u32 {
+        8
+    }
+
+    fn part1(&self, input: &str) -> Answer {
+        Ok(solve(input)?.len().to_string())
+    }
+
+    fn part2(&self, input: &str) -> Answer {
+        Ok(solve2(input)?.len().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{
+        collect_antinodes, diff_maps, parse_data, render, solve, solve2, solve_filtered,
+        solve_with, AntinodeRule, Day08, Point,
+    };
+    use crate::parallel::Mode;
+    use crate::solver::Solver;
+
+    #[test]
+    fn test_solution() {
+        let input = r"............
+........0...
+.....0......
+.......0....
+....0.......
+......A.....
+............
+............
+........A...
+.........A..
+............
+............";
+
+        let (data, board) = parse_data(input).unwrap();
+        assert_eq!(board, (12, 12));
+        assert_eq!(data.len(), 2);
+        assert!(data.get(&'A').is_some_and(|d| { d.len() == 3 }));
+
+        let solution = solve(input).unwrap();
+        assert_eq!(solution.len(), 14);
+        assert!(solution.contains(&Point::new(10, 10)));
+
+        let frequencies = data
+            .values()
+            .map(|nodes| nodes.as_slice())
+            .collect::<Vec<_>>();
+        for rule in [AntinodeRule::part1(), AntinodeRule::part2()] {
+            let sequential = collect_antinodes(&frequencies, &rule, board, Mode::Sequential);
+            for mode in Mode::all() {
+                assert_eq!(
+                    collect_antinodes(&frequencies, &rule, board, mode),
+                    sequential
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_invalid_map() {
+        assert!(Day08.part1("").is_err());
+        let error = solve("..a.\n..\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+    }
+
+    #[test]
+    fn test_solution2() {
+        let input = r"T.........
+...T......
+.T........
+..........
+..........
+..........
+..........
+..........
+..........
+..........";
+
+        let (data, _) = parse_data(input).unwrap();
+        assert_eq!(data.len(), 1);
+        assert!(data.get(&'T').is_some_and(|d| { d.len() == 3 }));
+
+        let solution = solve2(input).unwrap();
+        assert_eq!(solution.len(), 9);
+    }
+
+    #[test]
+    fn test_rule_variants() {
+        let input = r"..........
+..........
+..........
+....a.....
+..........
+.....a....
+..........
+..........
+..........
+..........";
+
+        let solution = solve_with(input, &AntinodeRule::part1()).unwrap();
+        assert_eq!(
+            solution,
+            [Point::new(3, 1), Point::new(6, 7)].into_iter().collect()
+        );
+
+        let rule = AntinodeRule {
+            min_harmonic: 2,
+            max_harmonic: Some(3),
+            ..AntinodeRule::part1()
+        };
+        assert_eq!(
+            solve_with(input, &rule).unwrap(),
+            [Point::new(7, 9)].into_iter().collect()
+        );
+
+        let rule = AntinodeRule {
+            antennas: true,
+            ..AntinodeRule::part1()
+        };
+        assert_eq!(solve_with(input, &rule).unwrap().len(), 4);
+
+        // antennas 2 rows apart, third of the distance is not on the grid
+        let rule = AntinodeRule {
+            ratio: 3,
+            ..AntinodeRule::part1()
+        };
+        assert!(solve_with(input, &rule).unwrap().is_empty());
+
+        // the step (1, 2) can't be reduced any further
+        let rule = AntinodeRule {
+            reduce: true,
+            ..AntinodeRule::part2()
+        };
+        assert_eq!(solve_with(input, &rule).unwrap(), solve2(input).unwrap());
+    }
+
+    #[test]
+    fn test_negative_harmonics() {
+        let input = r"..........
+..........
+..........
+....a.....
+..........
+.....a....
+..........
+..........
+..........
+..........";
+
+        // harmonics before the grid are skipped instead of ending the walk
+        let rule = AntinodeRule {
+            min_harmonic: -3,
+            max_harmonic: Some(-1),
+            ..AntinodeRule::part1()
+        };
+        assert_eq!(
+            solve_with(input, &rule).unwrap(),
+            [
+                Point::new(3, 1),
+                Point::new(4, 3),
+                Point::new(5, 5),
+                Point::new(6, 7),
+                Point::new(7, 9),
+            ]
+            .into_iter()
+            .collect()
+        );
+
+        let rule = AntinodeRule {
+            min_harmonic: -3,
+            ..AntinodeRule::part2()
+        };
+        assert!(solve_with(input, &rule)
+            .unwrap()
+            .is_superset(&solve2(input).unwrap()));
+
+        let rule = AntinodeRule {
+            min_harmonic: 2,
+            max_harmonic: Some(1),
+            ..AntinodeRule::part1()
+        };
+        assert!(rule.check().is_err());
+        assert!(AntinodeRule::part2().check().is_ok());
+    }
+
+    #[test]
+    fn test_resonant_harmonics() {
+        let input = r"a.........
+..........
+..........
+..........
+..a.......
+..........
+..........
+..........
+..........
+..........";
+
+        // raw difference (2, 4) jumps over (1, 2) and (3, 6)
+        let raw = solve2(input).unwrap();
+        assert_eq!(
+            raw,
+            [Point::new(0, 0), Point::new(2, 4), Point::new(4, 8)]
+                .into_iter()
+                .collect()
+        );
+
+        // reduced step walking away from the other antenna still misses (1, 2)
+        let reduced = solve_with(
+            input,
+            &AntinodeRule {
+                reduce: true,
+                ..AntinodeRule::part2()
+            },
+        )
+        .unwrap();
+        assert!(!reduced.contains(&Point::new(1, 2)));
+        assert!(reduced.contains(&Point::new(3, 6)));
+
+        let resonant = solve_with(input, &AntinodeRule::resonant()).unwrap();
+        assert_eq!(
+            resonant,
+            [
+                Point::new(0, 0),
+                Point::new(1, 2),
+                Point::new(2, 4),
+                Point::new(3, 6),
+                Point::new(4, 8),
+            ]
+            .into_iter()
+            .collect()
+        );
+    }
+
+    #[test]
+    fn test_line_matches_pairs() {
+        let input = r"............
+........0...
+.....0......
+.......0....
+....0.......
+......A.....
+............
+............
+........A...
+.........A..
+............
+............";
+
+        // walking both ways from one antenna gives the same result as ordered pairs
+        let line = AntinodeRule {
+            line: true,
+            ..AntinodeRule::part2()
+        };
+        assert_eq!(solve_with(input, &line).unwrap(), solve2(input).unwrap());
+        assert_eq!(
+            solve_with(input, &AntinodeRule::resonant()).unwrap().len(),
+            34
+        );
+    }
+
+    #[test]
+    fn test_render() {
+        let input = r"............
+........0...
+.....0......
+.......0....
+....0.......
+......A.....
+............
+............
+........A...
+.........A..
+............
+............";
+
+        let expected = r"......#....#
+...#....0...
+....#0....#.
+..#....0....
+....0....#..
+.#....A.....
+...#........
+#......#....
+........A...
+.........A..
+..........#.
+..........#.";
+
+        assert_eq!(render(input, &solve(input).unwrap(), None), expected);
+
+        let antinodes = solve_filtered(input, &AntinodeRule::part1(), Some('A')).unwrap();
+        assert_eq!(antinodes.len(), 5);
+        let rows = render(input, &antinodes, Some('A'));
+        let rows = rows.lines().collect::<Vec<_>>();
+        assert_eq!(rows[1], "...#........");
+        assert_eq!(rows[2], "....#.......");
+        assert_eq!(rows[4], "............");
+        assert_eq!(rows[5], "......A.....");
+    }
+
+    #[test]
+    fn test_render_part2() {
+        let input = r"T.........
+...T......
+.T........
+..........
+..........
+..........
+..........
+..........
+..........
+..........";
+
+        let expected = r"T....#....
+...T......
+.T....#...
+.........#
+..#.......
+..........
+...#......
+..........
+....#.....
+..........";
+
+        let map = render(input, &solve2(input).unwrap(), None);
+        assert_eq!(map, expected);
+        assert!(diff_maps(&map, expected).ends_with("0 cells differ"));
+
+        let wrong = render(input, &solve(input).unwrap(), None);
+        let diff = diff_maps(&wrong, expected);
+        let lines = diff.lines().collect::<Vec<_>>();
+        assert_eq!(lines[0], "actual      expected  ");
+        assert_eq!(lines[1], "T....#....  T....#....");
+        assert_eq!(lines[4], "..........  .........#");
+        assert_eq!(lines[5], "         ^           ^");
+        assert_eq!(lines.last(), Some(&"3 cells differ"));
+    }
+}